@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use riven::models::match_v5::Participant;
+
+use crate::item_timeline::{is_completion, ItemData};
+use crate::match_analysis::champion_id_of;
+use crate::match_store::StoredMatch;
+use crate::timeline::{max_order, MatchTimeline};
+
+/// Tier-one and tier-two boots
+pub const BOOTS: [i32; 12] = [1001, 2422, 3005, 3006, 3009, 3010, 3013, 3020, 3047, 3111, 3117, 3158];
+
+/// Components with 3xxx ids that should not count as completed items. Only consulted when the
+/// patch's item data is unavailable; `item_timeline::is_completion` decides otherwise.
+const COMPONENTS: [i32; 28] = [
+    3024, 3035, 3044, 3051, 3057, 3066, 3067, 3070, 3076, 3082, 3086, 3108, 3112, 3113,
+    3114, 3123, 3133, 3134, 3140, 3145, 3155, 3177, 3184, 3211, 3801, 3802, 3916, 3340,
+];
+
+/// Completed items start at 3000; 1xxx are basic components and 2xxx consumables
+const MIN_COMPLETED_ITEM_ID: i32 = 3000;
+
+/// Number of first-completed items that define a build's core
+const CORE_ITEM_COUNT: usize = 3;
+
+/// Builds returned per source
+const MAX_BUILDS_PER_SOURCE: usize = 5;
+
+pub fn is_boots(item: i32) -> bool {
+    BOOTS.contains(&item)
+}
+
+pub fn is_completed_item(item: i32) -> bool {
+    item >= MIN_COMPLETED_ITEM_ID && !is_boots(item) && !COMPONENTS.contains(&item)
+}
+
+/// Map a role query ("top", "mid", "support", ...) to match-v5 `team_position`
+pub fn role_to_position(role: &str) -> Option<&'static str> {
+    match role.trim().to_lowercase().as_str() {
+        "top" => Some("TOP"),
+        "jungle" | "jg" => Some("JUNGLE"),
+        "middle" | "mid" => Some("MIDDLE"),
+        "bottom" | "bot" | "adc" => Some("BOTTOM"),
+        "support" | "utility" | "sup" => Some("UTILITY"),
+        _ => None,
+    }
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionBuild {
+    pub id: String,
+    pub name: String,
+    pub source: String,
+    pub role: Option<String>,
+    pub rank: String,
+    pub games: u32,
+    pub wins: u32,
+    pub pick_rate: f32,
+    pub win_rate: f32,
+    pub items: BuildItems,
+    pub runes: BuildRunes,
+    pub skill_order: Option<String>,
+    pub skill_sequence: Option<String>,
+    pub starting_items: Vec<i32>,
+    pub summoner_spells: Vec<i32>,
+    pub sample_size: u32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildItems {
+    pub core: Vec<i32>,
+    pub boots: Vec<i32>,
+    pub situational: Vec<i32>,
+}
+
+#[derive(Debug, serde::Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildRunes {
+    pub primary: PrimaryRunes,
+    pub secondary: SecondaryRunes,
+    pub shards: Vec<i32>,
+}
+
+#[derive(Debug, serde::Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrimaryRunes {
+    pub tree: i32,
+    pub keystone: i32,
+    pub runes: Vec<i32>,
+}
+
+#[derive(Debug, serde::Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SecondaryRunes {
+    pub tree: i32,
+    pub runes: Vec<i32>,
+}
+
+/// One participant's build in one game
+struct GameBuild {
+    core: Vec<i32>,
+    boots: Option<i32>,
+    extra_items: Vec<i32>,
+    runes: BuildRunes,
+    spells: Vec<i32>,
+    skill_sequence: Option<String>,
+    starting_items: Option<Vec<i32>>,
+    win: bool,
+}
+
+impl GameBuild {
+    fn signature(&self) -> String {
+        let mut core = self.core.clone();
+        core.sort_unstable();
+        format!(
+            "{}|{}|{:?}|{}|{:?}",
+            self.runes.primary.keystone,
+            self.runes.secondary.tree,
+            core,
+            self.boots.unwrap_or(0),
+            self.spells,
+        )
+    }
+}
+
+/// Rune selection from the match-v5 `perks` block
+pub fn extract_runes(participant: &Participant) -> BuildRunes {
+    let styles = &participant.perks.styles;
+    let primary = styles.iter()
+        .find(|s| s.description == "primaryStyle")
+        .or_else(|| styles.first());
+    let secondary = styles.iter()
+        .find(|s| s.description == "subStyle")
+        .or_else(|| styles.get(1));
+
+    let primary_perks: Vec<i32> = primary
+        .map(|s| s.selections.iter().map(|sel| sel.perk).collect())
+        .unwrap_or_default();
+    let stats = &participant.perks.stat_perks;
+
+    BuildRunes {
+        primary: PrimaryRunes {
+            tree: primary.map(|s| s.style).unwrap_or(0),
+            keystone: primary_perks.first().copied().unwrap_or(0),
+            runes: primary_perks.iter().skip(1).copied().collect(),
+        },
+        secondary: SecondaryRunes {
+            tree: secondary.map(|s| s.style).unwrap_or(0),
+            runes: secondary
+                .map(|s| s.selections.iter().map(|sel| sel.perk).collect())
+                .unwrap_or_default(),
+        },
+        shards: vec![stats.offense, stats.flex, stats.defense],
+    }
+}
+
+/// Final inventory (trinket excluded)
+pub fn final_items(participant: &Participant) -> Vec<i32> {
+    [
+        participant.item0,
+        participant.item1,
+        participant.item2,
+        participant.item3,
+        participant.item4,
+        participant.item5,
+    ]
+    .into_iter()
+    .filter(|&item| item != 0)
+    .collect()
+}
+
+fn extract_game_build(participant: &Participant, timeline: Option<&MatchTimeline>, item_data: Option<&ItemData>) -> GameBuild {
+    let items = final_items(participant);
+    let mut completed: Vec<i32> = items.iter().copied().filter(|&i| is_completion(i, item_data)).collect();
+
+    // With a timeline, order completed items by first purchase that wasn't undone; otherwise keep slot order
+    if let Some(timeline) = timeline {
+        let order = timeline.build_order(participant.participant_id);
+        completed.sort_by_key(|&item| {
+            order.iter()
+                .find(|e| !e.sold && e.item_id == item)
+                .map_or(i64::MAX, |e| e.timestamp)
+        });
+    }
+
+    let core: Vec<i32> = completed.iter().take(CORE_ITEM_COUNT).copied().collect();
+    let extra_items: Vec<i32> = completed.iter().skip(CORE_ITEM_COUNT).copied().collect();
+
+    let mut spells = vec![participant.summoner1_id, participant.summoner2_id];
+    spells.sort_unstable();
+
+    GameBuild {
+        core,
+        boots: items.iter().copied().find(|&i| is_boots(i)),
+        extra_items,
+        runes: extract_runes(participant),
+        spells,
+        skill_sequence: timeline
+            .map(|t| t.skill_sequence(participant.participant_id))
+            .filter(|s| !s.is_empty()),
+        starting_items: timeline
+            .map(|t| t.starting_items(participant.participant_id))
+            .filter(|items| !items.is_empty()),
+        win: participant.win,
+    }
+}
+
+/// Most frequent value, ties broken by first occurrence
+fn most_common<T: Clone + PartialEq>(values: &[T]) -> Option<T> {
+    let mut counts: Vec<(T, u32)> = Vec::new();
+    for value in values {
+        match counts.iter_mut().find(|(v, _)| v == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value.clone(), 1)),
+        }
+    }
+    counts.into_iter()
+        .enumerate()
+        .max_by_key(|(index, (_, count))| (*count, std::cmp::Reverse(*index)))
+        .map(|(_, (value, _))| value)
+}
+
+/// Champion, role and rank bucket a build request is scoped to
+pub struct BuildQuery<'a> {
+    pub champion_id: i32,
+    pub role: Option<&'a str>,
+    pub rank: &'a str,
+}
+
+/// Aggregate builds for a champion from stored matches, grouped by build signature.
+/// When `puuid` is set only that player's games are counted. Item data, when available,
+/// decides which items are finished.
+pub fn aggregate_builds(
+    stored: &[Arc<StoredMatch>],
+    query: &BuildQuery,
+    item_data: Option<&ItemData>,
+    puuid: Option<&str>,
+    source_key: &str,
+    source_label: &str,
+) -> Vec<ChampionBuild> {
+    let position = query.role.and_then(role_to_position);
+    let mut groups: HashMap<String, Vec<GameBuild>> = HashMap::new();
+
+    for entry in stored {
+        for participant in &entry.data.info.participants {
+            if champion_id_of(participant) != query.champion_id {
+                continue;
+            }
+            if position.is_some_and(|pos| participant.team_position != pos) {
+                continue;
+            }
+            if puuid.is_some_and(|id| participant.puuid != id) {
+                continue;
+            }
+            let build = extract_game_build(participant, entry.timeline.as_ref(), item_data);
+            groups.entry(build.signature()).or_default().push(build);
+        }
+    }
+
+    let mut groups: Vec<Vec<GameBuild>> = groups.into_values().collect();
+    groups.sort_by_key(|games| std::cmp::Reverse(games.len()));
+    let total_games: usize = groups.iter().map(|games| games.len()).sum();
+
+    groups.into_iter()
+        .take(MAX_BUILDS_PER_SOURCE)
+        .enumerate()
+        .map(|(index, games)| {
+            let first = &games[0];
+            let wins = games.iter().filter(|g| g.win).count() as u32;
+            let count = games.len() as u32;
+
+            let mut extra_counts: HashMap<i32, u32> = HashMap::new();
+            for game in &games {
+                for &item in &game.extra_items {
+                    *extra_counts.entry(item).or_insert(0) += 1;
+                }
+            }
+            let mut situational: Vec<(i32, u32)> = extra_counts.into_iter().collect();
+            situational.sort_by_key(|&(item, n)| (std::cmp::Reverse(n), item));
+
+            let sequences: Vec<String> = games.iter().filter_map(|g| g.skill_sequence.clone()).collect();
+            let max_orders: Vec<String> = sequences.iter().map(|s| max_order(s)).collect();
+            let starts: Vec<Vec<i32>> = games.iter().filter_map(|g| g.starting_items.clone()).collect();
+
+            ChampionBuild {
+                id: format!("{}_build_{}", source_key, index + 1),
+                name: if index == 0 {
+                    format!("{} Most Played", source_label)
+                } else {
+                    format!("{} Build {}", source_label, index + 1)
+                },
+                source: source_label.to_string(),
+                role: query.role.map(|r| r.to_string()),
+                rank: query.rank.to_string(),
+                games: count,
+                wins,
+                pick_rate: if total_games > 0 { count as f32 / total_games as f32 * 100.0 } else { 0.0 },
+                win_rate: wins as f32 / count as f32 * 100.0,
+                items: BuildItems {
+                    core: first.core.clone(),
+                    boots: first.boots.into_iter().collect(),
+                    situational: situational.into_iter().take(4).map(|(item, _)| item).collect(),
+                },
+                runes: most_common(&games.iter().map(|g| g.runes.clone()).collect::<Vec<_>>())
+                    .unwrap_or_else(|| first.runes.clone()),
+                skill_order: most_common(&max_orders),
+                skill_sequence: most_common(&sequences),
+                starting_items: most_common(&starts).unwrap_or_default(),
+                summoner_spells: first.spells.clone(),
+                sample_size: count,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use riven::models::match_v5::Participant;
+
+use crate::builds::{final_items, is_completed_item};
+use crate::match_analysis::{champion_id_of, creep_score, is_remake};
+use crate::match_store::StoredMatch;
+use crate::matchups::ban_rates;
+use crate::timeline::max_order;
+
+/// Games in the recent-form window
+const RECENT_FORM_GAMES: usize = 10;
+
+/// Win-rate swing (percentage points) between recent and older games that counts as a trend
+const TREND_THRESHOLD: f32 = 10.0;
+
+/// Items listed in each item table
+const MAX_ITEM_ENTRIES: usize = 5;
+
+/// Items need this many games before they can appear under highest win rate
+const MIN_ITEM_GAMES: u32 = 3;
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DetailedChampionStats {
+    pub champion_id: i32,
+    pub champion_name: String,
+    pub user_stats: UserChampionStats,
+    pub global_stats: GlobalChampionStats,
+    pub role_performance: HashMap<String, RolePerformance>,
+    pub item_stats: ItemStats,
+    pub skill_order: SkillOrderStats,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserChampionStats {
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub win_rate: f32,
+    pub kda: KdaStats,
+    pub average_stats: AverageStats,
+    pub mastery_info: Option<MasteryInfo>,
+    pub recent_form: RecentForm,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KdaStats {
+    pub kills: f32,
+    pub deaths: f32,
+    pub assists: f32,
+    pub ratio: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AverageStats {
+    pub cs: f32,
+    pub cs_per_minute: f32,
+    pub gold: f32,
+    pub gold_per_minute: f32,
+    pub damage: f32,
+    pub damage_to_champions: f32,
+    pub damage_per_minute: f32,
+    pub vision_score: f32,
+    pub vision_per_minute: f32,
+    pub game_length: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MasteryInfo {
+    pub level: i32,
+    pub points: i32,
+    pub tokens_earned: i32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentForm {
+    pub last10_games: FormWindow,
+    pub trend: String,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FormWindow {
+    pub wins: u32,
+    pub losses: u32,
+    pub win_rate: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalChampionStats {
+    pub games: u32,
+    pub pick_rate: f32,
+    pub ban_rate: f32,
+    pub win_rate: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RolePerformance {
+    pub games_played: u32,
+    pub win_rate: f32,
+    pub kda: f32,
+    pub primary_role: bool,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemStats {
+    pub most_built: Vec<ItemEntry>,
+    pub highest_win_rate: Vec<ItemEntry>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemEntry {
+    pub item_id: i32,
+    pub games: u32,
+    pub build_rate: f32,
+    pub win_rate: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillOrderStats {
+    pub user_preference: Option<String>,
+    pub highest_win_rate: Option<String>,
+    pub sample_size: u32,
+}
+
+fn kda_ratio(kills: f32, deaths: f32, assists: f32) -> f32 {
+    (kills + assists) / deaths.max(1.0)
+}
+
+fn win_rate(wins: u32, games: u32) -> f32 {
+    if games > 0 { wins as f32 / games as f32 * 100.0 } else { 0.0 }
+}
+
+fn form_window(games: &[&Participant]) -> FormWindow {
+    let wins = games.iter().filter(|p| p.win).count() as u32;
+    let total = games.len() as u32;
+    FormWindow {
+        wins,
+        losses: total - wins,
+        win_rate: win_rate(wins, total),
+    }
+}
+
+/// "improving"/"declining" when the recent window's win rate differs from the
+/// older games by more than the threshold, otherwise "stable"
+fn form_trend(recent: &FormWindow, older: &[&Participant]) -> &'static str {
+    if older.is_empty() {
+        return "stable";
+    }
+    let older = form_window(older);
+    let delta = recent.win_rate - older.win_rate;
+    if delta > TREND_THRESHOLD {
+        "improving"
+    } else if delta < -TREND_THRESHOLD {
+        "declining"
+    } else {
+        "stable"
+    }
+}
+
+/// Aggregate the player's stored games on one champion. `corpus` should be the
+/// whole local corpus so pick, ban and win rates can be derived from it.
+pub fn compute_champion_stats(
+    corpus: &[Arc<StoredMatch>],
+    puuid: &str,
+    champion_id: i32,
+    mastery: Option<MasteryInfo>,
+) -> Option<DetailedChampionStats> {
+    let mut stored: Vec<&StoredMatch> = corpus.iter().map(|s| s.as_ref()).collect();
+    stored.sort_by_key(|s| std::cmp::Reverse(s.data.info.game_creation));
+
+    // (participant, game duration in minutes, stored match); remakes would skew per-minute stats
+    let games: Vec<(&Participant, f32, &StoredMatch)> = stored.iter()
+        .filter(|s| !is_remake(&s.data))
+        .filter_map(|s| {
+            s.data.info.participants.iter()
+                .find(|p| p.puuid == puuid && champion_id_of(p) == champion_id)
+                .map(|p| (p, (s.data.info.game_duration as f32 / 60.0).max(1.0), *s))
+        })
+        .collect();
+
+    let champion_name = games.first()?.0.champion_name.clone();
+    let count = games.len() as f32;
+    let wins = games.iter().filter(|(p, _, _)| p.win).count() as u32;
+    let total_minutes: f32 = games.iter().map(|(_, minutes, _)| minutes).sum();
+    let sum = |stat: fn(&Participant) -> f32| games.iter().map(|(p, _, _)| stat(p)).sum::<f32>();
+
+    let kills = sum(|p| p.kills as f32) / count;
+    let deaths = sum(|p| p.deaths as f32) / count;
+    let assists = sum(|p| p.assists as f32) / count;
+    let cs = sum(|p| creep_score(p) as f32);
+    let gold = sum(|p| p.gold_earned as f32);
+    let damage = sum(|p| p.total_damage_dealt as f32);
+    let damage_to_champions = sum(|p| p.total_damage_dealt_to_champions as f32);
+    let vision = sum(|p| p.vision_score as f32);
+
+    let participants: Vec<&Participant> = games.iter().map(|(p, _, _)| *p).collect();
+    let recent_count = participants.len().min(RECENT_FORM_GAMES);
+    let last10_games = form_window(&participants[..recent_count]);
+    let trend = form_trend(&last10_games, &participants[recent_count..]).to_string();
+
+    // Role split
+    let mut roles: HashMap<String, (u32, u32, f32, f32, f32)> = HashMap::new();
+    for p in &participants {
+        let role = if p.team_position.is_empty() { "unknown".to_string() } else { p.team_position.to_lowercase() };
+        let entry = roles.entry(role).or_insert((0, 0, 0.0, 0.0, 0.0));
+        entry.0 += 1;
+        if p.win {
+            entry.1 += 1;
+        }
+        entry.2 += p.kills as f32;
+        entry.3 += p.deaths as f32;
+        entry.4 += p.assists as f32;
+    }
+    let primary_games = roles.values().map(|r| r.0).max().unwrap_or(0);
+    let role_performance = roles.into_iter()
+        .map(|(role, (played, role_wins, k, d, a))| {
+            (role, RolePerformance {
+                games_played: played,
+                win_rate: win_rate(role_wins, played),
+                kda: kda_ratio(k, d, a),
+                primary_role: played == primary_games,
+            })
+        })
+        .collect();
+
+    // Completed items in the final inventory
+    let mut items: HashMap<i32, (u32, u32)> = HashMap::new();
+    for p in &participants {
+        for item in final_items(p).into_iter().filter(|&i| is_completed_item(i)) {
+            let entry = items.entry(item).or_insert((0, 0));
+            entry.0 += 1;
+            if p.win {
+                entry.1 += 1;
+            }
+        }
+    }
+    let item_entries: Vec<ItemEntry> = items.into_iter()
+        .map(|(item_id, (item_games, item_wins))| ItemEntry {
+            item_id,
+            games: item_games,
+            build_rate: item_games as f32 / count * 100.0,
+            win_rate: win_rate(item_wins, item_games),
+        })
+        .collect();
+    let mut most_built = item_entries.clone();
+    most_built.sort_by_key(|i| (std::cmp::Reverse(i.games), i.item_id));
+    most_built.truncate(MAX_ITEM_ENTRIES);
+    let mut highest_win_rate: Vec<ItemEntry> = item_entries.into_iter()
+        .filter(|i| i.games >= MIN_ITEM_GAMES)
+        .collect();
+    highest_win_rate.sort_by(|a, b| b.win_rate.total_cmp(&a.win_rate).then(b.games.cmp(&a.games)));
+    highest_win_rate.truncate(MAX_ITEM_ENTRIES);
+
+    // Skill max order, from games with a stored timeline
+    let mut orders: HashMap<String, (u32, u32)> = HashMap::new();
+    for (p, _, s) in &games {
+        if let Some(timeline) = &s.timeline {
+            let sequence = timeline.skill_sequence(p.participant_id);
+            if sequence.is_empty() {
+                continue;
+            }
+            let entry = orders.entry(max_order(&sequence)).or_insert((0, 0));
+            entry.0 += 1;
+            if p.win {
+                entry.1 += 1;
+            }
+        }
+    }
+    let skill_order = SkillOrderStats {
+        user_preference: orders.iter()
+            .max_by_key(|(order, (n, _))| (*n, std::cmp::Reverse((*order).clone())))
+            .map(|(order, _)| order.clone()),
+        highest_win_rate: orders.iter()
+            .max_by(|(_, (a_games, a_wins)), (_, (b_games, b_wins))| {
+                win_rate(*a_wins, *a_games)
+                    .total_cmp(&win_rate(*b_wins, *b_games))
+                    .then(a_games.cmp(b_games))
+            })
+            .map(|(order, _)| order.clone()),
+        sample_size: orders.values().map(|(n, _)| n).sum(),
+    };
+
+    // Corpus-wide pick, ban and win rates
+    let corpus_picks: Vec<&Participant> = stored.iter()
+        .flat_map(|s| s.data.info.participants.iter())
+        .filter(|p| champion_id_of(p) == champion_id)
+        .collect();
+    let corpus_wins = corpus_picks.iter().filter(|p| p.win).count() as u32;
+    let corpus_games = corpus.len().max(1) as f32;
+    let global_stats = GlobalChampionStats {
+        games: corpus_picks.len() as u32,
+        pick_rate: corpus_picks.len() as f32 / corpus_games * 100.0,
+        ban_rate: ban_rates(corpus).get(&champion_id).copied().unwrap_or(0.0),
+        win_rate: win_rate(corpus_wins, corpus_picks.len() as u32),
+    };
+
+    Some(DetailedChampionStats {
+        champion_id,
+        champion_name,
+        user_stats: UserChampionStats {
+            games_played: games.len() as u32,
+            wins,
+            losses: games.len() as u32 - wins,
+            win_rate: win_rate(wins, games.len() as u32),
+            kda: KdaStats { kills, deaths, assists, ratio: kda_ratio(kills, deaths, assists) },
+            average_stats: AverageStats {
+                cs: cs / count,
+                cs_per_minute: cs / total_minutes,
+                gold: gold / count,
+                gold_per_minute: gold / total_minutes,
+                damage: damage / count,
+                damage_to_champions: damage_to_champions / count,
+                damage_per_minute: damage_to_champions / total_minutes,
+                vision_score: vision / count,
+                vision_per_minute: vision / total_minutes,
+                game_length: total_minutes / count,
+            },
+            mastery_info: mastery,
+            recent_form: RecentForm { last10_games, trend },
+        },
+        global_stats,
+        role_performance,
+        item_stats: ItemStats { most_built, highest_win_rate },
+        skill_order,
+    })
+}
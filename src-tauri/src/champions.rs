@@ -0,0 +1,207 @@
+/// Static champion data: ids, display names, usual roles and Data Dragon class tags
+#[derive(Debug, Clone, Copy)]
+pub struct ChampionInfo {
+    pub id: i32,
+    pub name: &'static str,
+    pub key: &'static str,
+    pub title: &'static str,
+    pub roles: &'static [&'static str],
+    pub tags: &'static [&'static str],
+}
+
+impl ChampionInfo {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag)
+    }
+
+    /// First Data Dragon tag, the champion's primary class
+    pub fn primary_tag(&self) -> &'static str {
+        self.tags.first().copied().unwrap_or("")
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "name": self.name,
+            "key": self.key,
+            "title": self.title,
+            "roles": self.roles,
+            "tags": self.tags,
+        })
+    }
+}
+
+pub fn champion_by_id(id: i32) -> Option<&'static ChampionInfo> {
+    CHAMPIONS.iter().find(|c| c.id == id)
+}
+
+/// Comprehensive champion list as of July 2025
+pub const CHAMPIONS: &[ChampionInfo] = &[
+    ChampionInfo { id: 266, name: "Aatrox", key: "Aatrox", title: "the Darkin Blade", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 103, name: "Ahri", key: "Ahri", title: "the Nine-Tailed Fox", roles: &["middle"], tags: &["Mage", "Assassin"] },
+    ChampionInfo { id: 84, name: "Akali", key: "Akali", title: "the Rogue Assassin", roles: &["middle", "top"], tags: &["Assassin"] },
+    ChampionInfo { id: 166, name: "Akshan", key: "Akshan", title: "the Rogue Sentinel", roles: &["middle", "top"], tags: &["Marksman", "Assassin"] },
+    ChampionInfo { id: 12, name: "Alistar", key: "Alistar", title: "the Minotaur", roles: &["support"], tags: &["Tank", "Support"] },
+    ChampionInfo { id: 32, name: "Amumu", key: "Amumu", title: "the Sad Mummy", roles: &["jungle"], tags: &["Tank", "Mage"] },
+    ChampionInfo { id: 34, name: "Anivia", key: "Anivia", title: "the Cryophoenix", roles: &["middle"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 1, name: "Annie", key: "Annie", title: "the Dark Child", roles: &["middle"], tags: &["Mage"] },
+    ChampionInfo { id: 523, name: "Aphelios", key: "Aphelios", title: "the Weapon of the Faithful", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 22, name: "Ashe", key: "Ashe", title: "the Frost Archer", roles: &["bottom"], tags: &["Marksman", "Support"] },
+    ChampionInfo { id: 136, name: "Aurelion Sol", key: "AurelionSol", title: "the Star Forger", roles: &["middle"], tags: &["Mage"] },
+    ChampionInfo { id: 893, name: "Aurora", key: "Aurora", title: "the Witch Between Worlds", roles: &["middle", "top"], tags: &["Mage", "Assassin"] },
+    ChampionInfo { id: 268, name: "Azir", key: "Azir", title: "the Emperor of the Sands", roles: &["middle"], tags: &["Mage", "Marksman"] },
+    ChampionInfo { id: 432, name: "Bard", key: "Bard", title: "the Wandering Caretaker", roles: &["support"], tags: &["Support", "Mage"] },
+    ChampionInfo { id: 200, name: "Bel'Veth", key: "Belveth", title: "the Empress of the Void", roles: &["jungle"], tags: &["Fighter"] },
+    ChampionInfo { id: 53, name: "Blitzcrank", key: "Blitzcrank", title: "the Great Steam Golem", roles: &["support"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 63, name: "Brand", key: "Brand", title: "the Burning Vengeance", roles: &["support", "middle"], tags: &["Mage"] },
+    ChampionInfo { id: 201, name: "Braum", key: "Braum", title: "the Heart of the Freljord", roles: &["support"], tags: &["Support", "Tank"] },
+    ChampionInfo { id: 233, name: "Briar", key: "Briar", title: "the Restrained Hunger", roles: &["jungle"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 51, name: "Caitlyn", key: "Caitlyn", title: "the Sheriff of Piltover", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 164, name: "Camille", key: "Camille", title: "the Steel Shadow", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 69, name: "Cassiopeia", key: "Cassiopeia", title: "the Serpent's Embrace", roles: &["middle"], tags: &["Mage"] },
+    ChampionInfo { id: 31, name: "Cho'Gath", key: "Chogath", title: "the Terror of the Void", roles: &["top"], tags: &["Tank", "Mage"] },
+    ChampionInfo { id: 42, name: "Corki", key: "Corki", title: "the Daring Bombardier", roles: &["middle"], tags: &["Marksman"] },
+    ChampionInfo { id: 122, name: "Darius", key: "Darius", title: "the Hand of Noxus", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 131, name: "Diana", key: "Diana", title: "Scorn of the Moon", roles: &["jungle", "middle"], tags: &["Fighter", "Mage"] },
+    ChampionInfo { id: 119, name: "Draven", key: "Draven", title: "the Glorious Executioner", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 36, name: "Dr. Mundo", key: "DrMundo", title: "the Madman of Zaun", roles: &["top", "jungle"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 245, name: "Ekko", key: "Ekko", title: "the Boy Who Shattered Time", roles: &["jungle", "middle"], tags: &["Assassin", "Fighter"] },
+    ChampionInfo { id: 60, name: "Elise", key: "Elise", title: "the Spider Queen", roles: &["jungle"], tags: &["Mage", "Fighter"] },
+    ChampionInfo { id: 28, name: "Evelynn", key: "Evelynn", title: "Agony's Embrace", roles: &["jungle"], tags: &["Assassin", "Mage"] },
+    ChampionInfo { id: 81, name: "Ezreal", key: "Ezreal", title: "the Prodigal Explorer", roles: &["bottom"], tags: &["Marksman", "Mage"] },
+    ChampionInfo { id: 9, name: "Fiddlesticks", key: "Fiddlesticks", title: "the Ancient Fear", roles: &["jungle"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 114, name: "Fiora", key: "Fiora", title: "the Grand Duelist", roles: &["top"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 105, name: "Fizz", key: "Fizz", title: "the Tidal Trickster", roles: &["middle"], tags: &["Assassin", "Fighter"] },
+    ChampionInfo { id: 3, name: "Galio", key: "Galio", title: "the Colossus", roles: &["middle", "support"], tags: &["Tank", "Mage"] },
+    ChampionInfo { id: 41, name: "Gangplank", key: "Gangplank", title: "the Saltwater Scourge", roles: &["top"], tags: &["Fighter"] },
+    ChampionInfo { id: 86, name: "Garen", key: "Garen", title: "The Might of Demacia", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 150, name: "Gnar", key: "Gnar", title: "the Missing Link", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 79, name: "Gragas", key: "Gragas", title: "the Rabble Rouser", roles: &["jungle"], tags: &["Fighter", "Mage"] },
+    ChampionInfo { id: 104, name: "Graves", key: "Graves", title: "the Outlaw", roles: &["jungle"], tags: &["Marksman"] },
+    ChampionInfo { id: 887, name: "Gwen", key: "Gwen", title: "The Hallowed Seamstress", roles: &["top"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 120, name: "Hecarim", key: "Hecarim", title: "the Shadow of War", roles: &["jungle"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 74, name: "Heimerdinger", key: "Heimerdinger", title: "the Revered Inventor", roles: &["middle", "support"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 910, name: "Hwei", key: "Hwei", title: "the Visionary", roles: &["middle", "support"], tags: &["Mage"] },
+    ChampionInfo { id: 420, name: "Illaoi", key: "Illaoi", title: "the Kraken Priestess", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 39, name: "Irelia", key: "Irelia", title: "the Blade Dancer", roles: &["top", "middle"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 427, name: "Ivern", key: "Ivern", title: "the Green Father", roles: &["jungle"], tags: &["Support", "Mage"] },
+    ChampionInfo { id: 40, name: "Janna", key: "Janna", title: "the Storm's Fury", roles: &["support"], tags: &["Support", "Mage"] },
+    ChampionInfo { id: 59, name: "Jarvan IV", key: "JarvanIV", title: "the Exemplar of Demacia", roles: &["jungle"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 24, name: "Jax", key: "Jax", title: "Grandmaster at Arms", roles: &["top", "jungle"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 126, name: "Jayce", key: "Jayce", title: "the Defender of Tomorrow", roles: &["top", "middle"], tags: &["Fighter", "Marksman"] },
+    ChampionInfo { id: 202, name: "Jhin", key: "Jhin", title: "the Virtuoso", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 222, name: "Jinx", key: "Jinx", title: "the Loose Cannon", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 145, name: "Kai'Sa", key: "Kaisa", title: "Daughter of the Void", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 429, name: "Kalista", key: "Kalista", title: "the Spear of Vengeance", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 43, name: "Karma", key: "Karma", title: "the Enlightened One", roles: &["support", "middle"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 30, name: "Karthus", key: "Karthus", title: "the Deathsinger", roles: &["jungle"], tags: &["Mage"] },
+    ChampionInfo { id: 38, name: "Kassadin", key: "Kassadin", title: "the Void Walker", roles: &["middle"], tags: &["Assassin", "Mage"] },
+    ChampionInfo { id: 55, name: "Katarina", key: "Katarina", title: "the Sinister Blade", roles: &["middle"], tags: &["Assassin", "Mage"] },
+    ChampionInfo { id: 10, name: "Kayle", key: "Kayle", title: "the Righteous", roles: &["top"], tags: &["Fighter", "Support"] },
+    ChampionInfo { id: 141, name: "Kayn", key: "Kayn", title: "the Shadow Reaper", roles: &["jungle"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 85, name: "Kennen", key: "Kennen", title: "the Heart of the Tempest", roles: &["top"], tags: &["Mage", "Marksman"] },
+    ChampionInfo { id: 121, name: "Kha'Zix", key: "Khazix", title: "the Voidreaver", roles: &["jungle"], tags: &["Assassin"] },
+    ChampionInfo { id: 203, name: "Kindred", key: "Kindred", title: "The Eternal Hunters", roles: &["jungle"], tags: &["Marksman"] },
+    ChampionInfo { id: 240, name: "Kled", key: "Kled", title: "the Cantankerous Cavalier", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 96, name: "Kog'Maw", key: "KogMaw", title: "the Mouth of the Abyss", roles: &["bottom"], tags: &["Marksman", "Mage"] },
+    ChampionInfo { id: 897, name: "K'Sante", key: "KSante", title: "the Pride of Nazumah", roles: &["top"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 7, name: "LeBlanc", key: "Leblanc", title: "the Deceiver", roles: &["middle"], tags: &["Assassin", "Mage"] },
+    ChampionInfo { id: 64, name: "Lee Sin", key: "LeeSin", title: "the Blind Monk", roles: &["jungle"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 89, name: "Leona", key: "Leona", title: "the Radiant Dawn", roles: &["support"], tags: &["Tank", "Support"] },
+    ChampionInfo { id: 876, name: "Lillia", key: "Lillia", title: "the Bashful Bloom", roles: &["jungle"], tags: &["Fighter", "Mage"] },
+    ChampionInfo { id: 127, name: "Lissandra", key: "Lissandra", title: "the Ice Witch", roles: &["middle"], tags: &["Mage"] },
+    ChampionInfo { id: 236, name: "Lucian", key: "Lucian", title: "the Purifier", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 117, name: "Lulu", key: "Lulu", title: "the Fae Sorceress", roles: &["support"], tags: &["Support", "Mage"] },
+    ChampionInfo { id: 99, name: "Lux", key: "Lux", title: "the Lady of Luminosity", roles: &["support", "middle"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 54, name: "Malphite", key: "Malphite", title: "Shard of the Monolith", roles: &["top"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 90, name: "Malzahar", key: "Malzahar", title: "the Prophet of the Void", roles: &["middle"], tags: &["Mage", "Assassin"] },
+    ChampionInfo { id: 57, name: "Maokai", key: "Maokai", title: "the Twisted Treant", roles: &["support", "jungle"], tags: &["Tank", "Mage"] },
+    ChampionInfo { id: 11, name: "Master Yi", key: "MasterYi", title: "the Wuju Bladesman", roles: &["jungle"], tags: &["Assassin", "Fighter"] },
+    ChampionInfo { id: 902, name: "Milio", key: "Milio", title: "the Gentle Flame", roles: &["support"], tags: &["Support"] },
+    ChampionInfo { id: 21, name: "Miss Fortune", key: "MissFortune", title: "the Bounty Hunter", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 62, name: "Wukong", key: "MonkeyKing", title: "the Monkey King", roles: &["top", "jungle"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 82, name: "Mordekaiser", key: "Mordekaiser", title: "the Iron Revenant", roles: &["top"], tags: &["Fighter"] },
+    ChampionInfo { id: 25, name: "Morgana", key: "Morgana", title: "the Fallen", roles: &["support"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 267, name: "Nami", key: "Nami", title: "the Tidecaller", roles: &["support"], tags: &["Support", "Mage"] },
+    ChampionInfo { id: 75, name: "Nasus", key: "Nasus", title: "the Curator of the Sands", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 111, name: "Nautilus", key: "Nautilus", title: "the Titan of the Depths", roles: &["support"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 518, name: "Neeko", key: "Neeko", title: "the Curious Chameleon", roles: &["middle", "support"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 76, name: "Nidalee", key: "Nidalee", title: "the Bestial Huntress", roles: &["jungle"], tags: &["Assassin", "Mage"] },
+    ChampionInfo { id: 895, name: "Nilah", key: "Nilah", title: "the Joy Unbound", roles: &["bottom"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 56, name: "Nocturne", key: "Nocturne", title: "the Eternal Nightmare", roles: &["jungle"], tags: &["Assassin", "Fighter"] },
+    ChampionInfo { id: 20, name: "Nunu & Willump", key: "Nunu", title: "the Boy and His Yeti", roles: &["jungle"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 2, name: "Olaf", key: "Olaf", title: "the Berserker", roles: &["top", "jungle"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 61, name: "Orianna", key: "Orianna", title: "the Lady of Clockwork", roles: &["middle"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 516, name: "Ornn", key: "Ornn", title: "The Fire Beneath the Mountain", roles: &["top"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 80, name: "Pantheon", key: "Pantheon", title: "the Unbreakable Spear", roles: &["middle", "support"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 78, name: "Poppy", key: "Poppy", title: "Keeper of the Hammer", roles: &["jungle", "top"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 555, name: "Pyke", key: "Pyke", title: "the Bloodharbor Ripper", roles: &["support"], tags: &["Support", "Assassin"] },
+    ChampionInfo { id: 246, name: "Qiyana", key: "Qiyana", title: "Empress of the Elements", roles: &["middle"], tags: &["Assassin", "Fighter"] },
+    ChampionInfo { id: 133, name: "Quinn", key: "Quinn", title: "Demacia's Wings", roles: &["top"], tags: &["Marksman", "Assassin"] },
+    ChampionInfo { id: 497, name: "Rakan", key: "Rakan", title: "The Charmer", roles: &["support"], tags: &["Support"] },
+    ChampionInfo { id: 33, name: "Rammus", key: "Rammus", title: "the Armordillo", roles: &["jungle"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 421, name: "Rek'Sai", key: "RekSai", title: "the Void Burrower", roles: &["jungle"], tags: &["Fighter"] },
+    ChampionInfo { id: 526, name: "Rell", key: "Rell", title: "the Iron Maiden", roles: &["support"], tags: &["Tank", "Support"] },
+    ChampionInfo { id: 888, name: "Renata Glasc", key: "Renata", title: "the Chem-Baroness", roles: &["support"], tags: &["Support", "Mage"] },
+    ChampionInfo { id: 58, name: "Renekton", key: "Renekton", title: "the Butcher of the Sands", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 107, name: "Rengar", key: "Rengar", title: "the Pridestalker", roles: &["jungle"], tags: &["Assassin", "Fighter"] },
+    ChampionInfo { id: 92, name: "Riven", key: "Riven", title: "the Exile", roles: &["top"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 68, name: "Rumble", key: "Rumble", title: "the Mechanized Menace", roles: &["top"], tags: &["Fighter", "Mage"] },
+    ChampionInfo { id: 13, name: "Ryze", key: "Ryze", title: "the Rune Mage", roles: &["middle"], tags: &["Mage", "Fighter"] },
+    ChampionInfo { id: 360, name: "Samira", key: "Samira", title: "the Desert Rose", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 113, name: "Sejuani", key: "Sejuani", title: "Fury of the North", roles: &["jungle"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 235, name: "Senna", key: "Senna", title: "the Redeemer", roles: &["support", "bottom"], tags: &["Marksman", "Support"] },
+    ChampionInfo { id: 147, name: "Seraphine", key: "Seraphine", title: "the Starry-Eyed Songstress", roles: &["support", "middle"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 875, name: "Sett", key: "Sett", title: "the Boss", roles: &["top", "support"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 35, name: "Shaco", key: "Shaco", title: "the Demon Jester", roles: &["jungle"], tags: &["Assassin"] },
+    ChampionInfo { id: 98, name: "Shen", key: "Shen", title: "the Eye of Twilight", roles: &["top"], tags: &["Tank"] },
+    ChampionInfo { id: 102, name: "Shyvana", key: "Shyvana", title: "the Half-Dragon", roles: &["jungle"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 27, name: "Singed", key: "Singed", title: "the Mad Chemist", roles: &["top"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 14, name: "Sion", key: "Sion", title: "The Undead Juggernaut", roles: &["top"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 15, name: "Sivir", key: "Sivir", title: "the Battle Mistress", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 72, name: "Skarner", key: "Skarner", title: "the Crystal Vanguard", roles: &["jungle"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 901, name: "Smolder", key: "Smolder", title: "the Fiery Fledgling", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 37, name: "Sona", key: "Sona", title: "Maven of the Strings", roles: &["support"], tags: &["Support", "Mage"] },
+    ChampionInfo { id: 16, name: "Soraka", key: "Soraka", title: "the Starchild", roles: &["support"], tags: &["Support", "Mage"] },
+    ChampionInfo { id: 50, name: "Swain", key: "Swain", title: "the Noxian Grand General", roles: &["middle", "support"], tags: &["Mage", "Fighter"] },
+    ChampionInfo { id: 517, name: "Sylas", key: "Sylas", title: "the Unshackled", roles: &["middle", "jungle"], tags: &["Mage", "Assassin"] },
+    ChampionInfo { id: 134, name: "Syndra", key: "Syndra", title: "the Dark Sovereign", roles: &["middle"], tags: &["Mage"] },
+    ChampionInfo { id: 223, name: "Tahm Kench", key: "TahmKench", title: "the River King", roles: &["support", "top"], tags: &["Support", "Tank"] },
+    ChampionInfo { id: 163, name: "Taliyah", key: "Taliyah", title: "the Stoneweaver", roles: &["jungle", "middle"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 91, name: "Talon", key: "Talon", title: "the Blade's Shadow", roles: &["middle"], tags: &["Assassin"] },
+    ChampionInfo { id: 44, name: "Taric", key: "Taric", title: "the Shield of Valoran", roles: &["support"], tags: &["Support", "Fighter"] },
+    ChampionInfo { id: 17, name: "Teemo", key: "Teemo", title: "the Swift Scout", roles: &["top"], tags: &["Marksman", "Assassin"] },
+    ChampionInfo { id: 412, name: "Thresh", key: "Thresh", title: "the Chain Warden", roles: &["support"], tags: &["Support", "Fighter"] },
+    ChampionInfo { id: 18, name: "Tristana", key: "Tristana", title: "the Yordle Gunner", roles: &["bottom"], tags: &["Marksman", "Assassin"] },
+    ChampionInfo { id: 48, name: "Trundle", key: "Trundle", title: "the Troll King", roles: &["top", "jungle"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 23, name: "Tryndamere", key: "Tryndamere", title: "the Barbarian King", roles: &["top"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 4, name: "Twisted Fate", key: "TwistedFate", title: "the Card Master", roles: &["middle"], tags: &["Mage"] },
+    ChampionInfo { id: 29, name: "Twitch", key: "Twitch", title: "the Plague Rat", roles: &["bottom"], tags: &["Marksman", "Assassin"] },
+    ChampionInfo { id: 77, name: "Udyr", key: "Udyr", title: "the Spirit Walker", roles: &["jungle"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 6, name: "Urgot", key: "Urgot", title: "the Dreadnought", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 110, name: "Varus", key: "Varus", title: "the Arrow of Retribution", roles: &["bottom"], tags: &["Marksman", "Mage"] },
+    ChampionInfo { id: 67, name: "Vayne", key: "Vayne", title: "the Night Hunter", roles: &["bottom"], tags: &["Marksman", "Assassin"] },
+    ChampionInfo { id: 45, name: "Veigar", key: "Veigar", title: "the Tiny Master of Evil", roles: &["middle"], tags: &["Mage"] },
+    ChampionInfo { id: 161, name: "Vel'Koz", key: "Velkoz", title: "the Eye of the Void", roles: &["middle", "support"], tags: &["Mage"] },
+    ChampionInfo { id: 711, name: "Vex", key: "Vex", title: "the Gloomist", roles: &["middle"], tags: &["Mage"] },
+    ChampionInfo { id: 254, name: "Vi", key: "Vi", title: "the Piltover Enforcer", roles: &["jungle"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 234, name: "Viego", key: "Viego", title: "The Ruined King", roles: &["jungle"], tags: &["Assassin", "Fighter"] },
+    ChampionInfo { id: 112, name: "Viktor", key: "Viktor", title: "the Machine Herald", roles: &["middle"], tags: &["Mage"] },
+    ChampionInfo { id: 8, name: "Vladimir", key: "Vladimir", title: "the Crimson Reaper", roles: &["middle", "top"], tags: &["Mage"] },
+    ChampionInfo { id: 106, name: "Volibear", key: "Volibear", title: "the Relentless Storm", roles: &["jungle", "top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 19, name: "Warwick", key: "Warwick", title: "the Uncaged Wrath of Zaun", roles: &["jungle"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 498, name: "Xayah", key: "Xayah", title: "the Rebel", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 101, name: "Xerath", key: "Xerath", title: "the Magus Ascendant", roles: &["middle", "support"], tags: &["Mage"] },
+    ChampionInfo { id: 5, name: "Xin Zhao", key: "XinZhao", title: "the Seneschal of Demacia", roles: &["jungle"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 157, name: "Yasuo", key: "Yasuo", title: "the Unforgiven", roles: &["middle", "top"], tags: &["Fighter", "Assassin"] },
+    ChampionInfo { id: 777, name: "Yone", key: "Yone", title: "the Unforgotten", roles: &["middle", "top"], tags: &["Assassin", "Fighter"] },
+    ChampionInfo { id: 83, name: "Yorick", key: "Yorick", title: "Shepherd of Souls", roles: &["top"], tags: &["Fighter", "Tank"] },
+    ChampionInfo { id: 350, name: "Yuumi", key: "Yuumi", title: "the Magical Cat", roles: &["support"], tags: &["Support", "Mage"] },
+    ChampionInfo { id: 154, name: "Zac", key: "Zac", title: "the Secret Weapon", roles: &["jungle"], tags: &["Tank", "Fighter"] },
+    ChampionInfo { id: 238, name: "Zed", key: "Zed", title: "the Master of Shadows", roles: &["middle"], tags: &["Assassin"] },
+    ChampionInfo { id: 221, name: "Zeri", key: "Zeri", title: "The Spark of Zaun", roles: &["bottom"], tags: &["Marksman"] },
+    ChampionInfo { id: 115, name: "Ziggs", key: "Ziggs", title: "the Hexplosives Expert", roles: &["middle"], tags: &["Mage"] },
+    ChampionInfo { id: 26, name: "Zilean", key: "Zilean", title: "the Chronokeeper", roles: &["support"], tags: &["Support", "Mage"] },
+    ChampionInfo { id: 142, name: "Zoe", key: "Zoe", title: "the Aspect of Twilight", roles: &["middle"], tags: &["Mage", "Support"] },
+    ChampionInfo { id: 143, name: "Zyra", key: "Zyra", title: "Rise of the Thorns", roles: &["support"], tags: &["Mage", "Support"] },
+];
@@ -6,6 +6,36 @@ use std::collections::HashMap;
 use reqwest::Client;
 use tauri::{AppHandle, Emitter};
 use crate::riot_client::RiotClient;
+use crate::goals;
+use crate::playtime::playtime_heatmap;
+use crate::recommendations::recommend;
+use crate::sessions::analyze_sessions;
+use crate::sync;
+use crate::synergy::analyze_synergy;
+use crate::trends::analyze_trends;
+use crate::win_probability::{estimate_win_probability, PlayerFeatures, WinProbability};
+use crate::builds::{aggregate_builds, role_to_position, BuildQuery, ChampionBuild};
+use crate::champions::{champion_by_id, CHAMPIONS};
+use crate::champion_stats::{compute_champion_stats, MasteryInfo};
+use crate::composition::{compare_teams, composition_win_rates, group_teams, match_composition, CompositionReport, CompositionWinRate};
+use crate::comparison::{equalize_samples, shared_games, summarize_player, HeadToHead, PlayerSources};
+use crate::draft::{matchup_records, player_pool, suggest_picks, DraftInput, DraftSuggestion};
+use crate::encounters::{load_notes, lobby_encounters, set_note, LobbyEncounter, PlayerNote};
+use crate::heatmaps::{position_heatmaps, PositionHeatmaps};
+use crate::item_sets::{detect_league_install, write_item_sets};
+use crate::item_timeline::{item_data, item_timing_report, match_item_timeline, ItemData, ItemTimingReport, PlayerItemTimeline};
+use crate::lcu::LcuConnection;
+use crate::match_analysis::champion_id_of;
+use crate::match_store::RankFilter;
+use crate::matchups::{aggregate_matchups, ban_rates, classify_counters, MIN_MATCHUP_GAMES};
+use crate::premades::{detect_premades, LobbyMember, PremadeGroup};
+use crate::replays::{imported_games, library as replay_library, load_index, replay_folder, save_settings, scan_folder, ImportedGame, ReplayEntry, ReplaySettings};
+use crate::runes::{rune_data, validate_page, RunePage};
+use crate::scouting::{
+    cache_sources, cached_sources, champion_roles, likely_role, scout_player, PlayerScouting, ScoutingSources,
+    SCOUTING_CONCURRENCY, SCOUTING_GAMES,
+};
+use crate::vision::{vision_report, VisionReport};
 use riven::consts::QueueType;
 use chrono;
 
@@ -191,6 +221,8 @@ pub struct Tracked {
     pub region: Option<String>,
     pub puuid: Option<String>,
     pub in_game: bool,
+    /// match-v5 id of the game in progress, e.g. `EUW1_1234567890`
+    pub game_id: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -626,10 +658,15 @@ async fn calculate_performance_insights(
 
     let win_rate = (wins as f32 / total_games) * 100.0;
 
-    // Calculate recent form based on last 5 games
+    // Calculate recent form based on last 5 games, unless the current session matches a tilt pattern
     let recent_games = games.iter().take(5).collect::<Vec<_>>();
     let recent_wins = recent_games.iter().filter(|g| g.win).count();
-    let recent_form = if recent_wins >= 4 {
+    let tilted = analyze_sessions(&client.stored_history(puuid), puuid)
+        .current
+        .is_some_and(|c| c.tilted);
+    let recent_form = if tilted {
+        "tilted"
+    } else if recent_wins >= 4 {
         "hot"
     } else if recent_wins <= 1 {
         "cold"
@@ -722,6 +759,123 @@ pub async fn get_enhanced_traits() -> Result<serde_json::Value, String> {
     Ok(serde_json::to_value(enhanced_traits).unwrap())
 }
 
+/// Ranked entries, summoner, masteries and recent games for one live participant,
+/// reused from the scouting cache when the player was seen recently
+async fn participant_sources(client: &RiotClient, puuid: &str, region: &str) -> Arc<ScoutingSources> {
+    use log::warn;
+    if let Some(cached) = cached_sources(puuid) {
+        return cached;
+    }
+    // Hidden participants have no puuid to look up
+    if puuid.is_empty() {
+        return Arc::new(ScoutingSources { summoner: None, ranked: Vec::new(), masteries: Vec::new(), recent: Vec::new() });
+    }
+
+    let ranked = async {
+        match client.get_ranked_stats_with_retry(puuid, region).await {
+            Ok(ranked) => ranked,
+            Err(retry_err) => {
+                warn!("Failed to get ranked stats for participant after retries: {}", retry_err);
+                // Fallback to single attempt
+                client.get_ranked_stats(puuid, region).await.unwrap_or_default()
+            }
+        }
+    };
+    let (ranked, summoner, masteries, recent) = futures::future::join4(
+        ranked,
+        client.get_summoner_by_puuid(puuid, region),
+        client.get_champion_masteries(puuid, region),
+        client.get_recent_match_details(puuid, region, SCOUTING_GAMES),
+    )
+    .await;
+    let sources = Arc::new(ScoutingSources {
+        summoner: summoner.unwrap_or_else(|e| {
+            warn!("Failed to get summoner for participant: {:?}", e);
+            None
+        }),
+        ranked,
+        masteries: masteries.unwrap_or_else(|e| {
+            warn!("Failed to get masteries for participant: {:?}", e);
+            Vec::new()
+        }),
+        recent: recent.unwrap_or_else(|e| {
+            warn!("Failed to get recent matches for participant: {:?}", e);
+            Vec::new()
+        }),
+    });
+    cache_sources(puuid, sources.clone());
+    sources
+}
+
+/// Emit `gameStarted` straight away, then scout the lobby and emit `matchData`
+async fn emit_game_start(
+    app: &AppHandle,
+    state: &State,
+    puuid: &str,
+    region: &str,
+    game: riven::models::spectator_v5::CurrentGameInfo,
+) {
+    use futures::StreamExt;
+
+    let lobby: Vec<String> = game.participants.iter()
+        .filter_map(|p| p.puuid.clone())
+        .collect();
+    let encounters = lobby_encounters(
+        &state.client.store().matches_for(puuid),
+        puuid,
+        &lobby,
+        &load_notes(),
+    );
+    let _ = app.emit("gameStarted", Some(GameStartedPayload {
+        game: game.clone(),
+        encounters,
+    }));
+
+    // Participants are scouted a few at a time so the lobby doesn't exhaust the rate limit at once
+    let sources: Vec<Arc<ScoutingSources>> = futures::stream::iter(&game.participants)
+        .map(|p| participant_sources(&state.client, p.puuid.as_deref().unwrap_or(""), region))
+        .buffered(SCOUTING_CONCURRENCY)
+        .collect()
+        .await;
+
+    let champion_roles = state.client.store().with_matches(|matches| champion_roles(matches));
+    let mut ranked = Vec::new();
+    let mut scouting: Vec<PlayerScouting> = Vec::new();
+    let mut members: Vec<LobbyMember> = Vec::new();
+    let mut features: Vec<PlayerFeatures> = Vec::new();
+    for (p, sources) in game.participants.iter().zip(&sources) {
+        let participant_puuid = p.puuid.as_deref().unwrap_or("");
+        let champion_id = p.champion_id.0 as i32;
+        let team_id = p.team_id as i32;
+        let likely = likely_role(champion_id, [p.spell1_id, p.spell2_id], &champion_roles);
+        scouting.push(scout_player(participant_puuid, champion_id, likely, sources));
+        members.push(LobbyMember::new(participant_puuid, team_id, &sources.recent));
+        features.push(PlayerFeatures::new(
+            participant_puuid,
+            team_id,
+            champion_id,
+            &sources.ranked,
+            &sources.masteries,
+            &sources.recent,
+        ));
+        ranked.push(sources.ranked.clone());
+    }
+    let premades = detect_premades(&members, puuid);
+    let win_probability = estimate_win_probability(&features, &premades, puuid);
+
+    let teams = group_teams(game.participants.iter().map(|p| (p.team_id as i32, p.champion_id.0 as i32)));
+
+    let payload = MatchPayload {
+        game,
+        ranked,
+        scouting,
+        premades,
+        win_probability,
+        composition: compare_teams(&teams),
+    };
+    let _ = app.emit("matchData", Some(payload));
+}
+
 pub async fn poll_loop(app: AppHandle, state: Arc<State>) {
     use log::{info, warn, error};
     let mut consecutive_failures = 0;
@@ -738,63 +892,18 @@ pub async fn poll_loop(app: AppHandle, state: Arc<State>) {
             match state.client.get_active_game_with_retry(&puuid, region_str).await {
                 Ok(Some(game)) => {
                     consecutive_failures = 0; // Reset failure counter on success
-                    let mut t = state.inner.lock().await;
-                    if !t.in_game {
-                        t.in_game = true;
-
-                        let _ = app.emit("gameStarted", Some(game.clone()));
-
-                        // Fetch additional match data with retry logic
-                        let ranked_futs = game
-                            .participants
-                            .iter()
-                            .map(|p| async {
-                                match state
-                                    .client
-                                    .get_ranked_stats_with_retry(p.puuid.as_deref().unwrap_or(""), region_str)
-                                    .await
-                                {
-                                    Ok(ranked) => ranked,
-                                    Err(retry_err) => {
-                                        warn!("Failed to get ranked stats for participant after retries: {}", retry_err);
-                                        // Fallback to single attempt
-                                        state
-                                            .client
-                                            .get_ranked_stats(p.puuid.as_deref().unwrap_or(""), region_str)
-                                            .await
-                                            .unwrap_or_default()
-                                    }
-                                }
-                            });
-                        let ranked: Vec<_> = futures::future::join_all(ranked_futs)
-                            .await;
-
-                        let trait_futs = game
-                            .participants
-                            .iter()
-                            .map(|p| async {
-                                match state
-                                    .client
-                                    .calculate_traits(p.puuid.as_deref().unwrap_or(""), region_str)
-                                    .await
-                                {
-                                    Ok(traits) => traits,
-                                    Err(err) => {
-                                        warn!("Failed to calculate traits for participant: {:?}", err);
-                                        Vec::new()
-                                    }
-                                }
-                            });
-                        let traits: Vec<Vec<String>> = futures::future::join_all(trait_futs)
-                            .await;
-
-                        let payload = MatchPayload {
-                            game,
-                            ranked,
-                            traits,
-                        };
-
-                        let _ = app.emit("matchData", Some(payload));
+                    // Only flag the game under the lock; scouting runs after it is released
+                    let started = {
+                        let mut t = state.inner.lock().await;
+                        let started = !t.in_game;
+                        if started {
+                            t.in_game = true;
+                            t.game_id = Some(format!("{}_{}", game.platform_id, game.game_id));
+                        }
+                        started
+                    };
+                    if started {
+                        emit_game_start(&app, &state, &puuid, region_str, game).await;
                     }
                 }
 
@@ -804,6 +913,8 @@ pub async fn poll_loop(app: AppHandle, state: Arc<State>) {
                     if t.in_game {
                         t.in_game = false;
                         let _ = app.emit("gameEnded", Some(()));
+                        let match_id = t.game_id.take();
+                        spawn_post_game(app.clone(), state.clone(), puuid.clone(), region_str.to_string(), match_id);
                     } else {
                         let _ = app.emit("noGame", Some(()));
                     }
@@ -851,11 +962,125 @@ pub async fn poll_loop(app: AppHandle, state: Arc<State>) {
     }
 }
 
+/// Bring goal progress up to date with the stored matches and announce newly counted games
+fn check_goals(app: &AppHandle, state: &State, puuid: &str) {
+    let updates = goals::update_goals(puuid, &state.client.stored_history(puuid));
+    if !updates.is_empty() {
+        let _ = app.emit("goalProgress", Some(updates));
+    }
+}
+
+/// Run a match sync in the background; a sync that is already running is left alone
+fn spawn_match_sync(app: AppHandle, state: Arc<State>, puuid: String, region: String) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = sync::run_sync(&app, &state.client, &puuid, &region).await {
+            log::warn!("Match sync did not complete: {}", e);
+        }
+        check_goals(&app, &state, &puuid);
+    });
+}
+
+/// match-v5 usually lists a finished game a few minutes after it ends; waits between lookups
+const FINISHED_MATCH_RETRY_SECS: [u64; 8] = [30, 30, 60, 60, 120, 120, 300, 300];
+
+/// Poll until the finished match is stored, backing off between attempts
+async fn wait_for_finished_match(state: &State, puuid: &str, match_id: &str, region: &str) -> bool {
+    for delay in FINISHED_MATCH_RETRY_SECS {
+        tokio::time::sleep(Duration::from_secs(delay)).await;
+        match state.client.get_match_cached(match_id, region).await {
+            Ok(Some(_)) => {
+                if let Some(tier) = state.client.solo_tier(puuid, region).await {
+                    state.client.store().tag_rank(match_id, &tier);
+                }
+                return true;
+            }
+            Ok(None) => log::info!("Finished match {} not available yet", match_id),
+            Err(e) => log::warn!("Failed to fetch finished match {}: {:?}", match_id, e),
+        }
+    }
+    false
+}
+
+/// After a game: wait for the finished match to be stored, check the session for tilt,
+/// then sync the rest
+fn spawn_post_game(app: AppHandle, state: Arc<State>, puuid: String, region: String, match_id: Option<String>) {
+    tauri::async_runtime::spawn(async move {
+        let stored = match &match_id {
+            Some(match_id) => wait_for_finished_match(&state, &puuid, match_id, &region).await,
+            None => false,
+        };
+
+        if stored {
+            let history = state.client.stored_history(&puuid);
+            if let Some(current) = analyze_sessions(&history, &puuid).current {
+                if current.tilted {
+                    let _ = app.emit("tiltWarning", Some(current));
+                }
+            }
+
+            check_goals(&app, &state, &puuid);
+        } else {
+            log::warn!("Finished match {:?} was not stored; skipping post-game checks", match_id);
+        }
+
+        if let Err(e) = sync::run_sync(&app, &state.client, &puuid, &region).await {
+            log::warn!("Match sync did not complete: {}", e);
+        }
+        check_goals(&app, &state, &puuid);
+    });
+}
+
+/// Start a background backfill and incremental sync of the tracked player's match history
+#[tauri::command]
+pub async fn start_match_sync(app: AppHandle) -> Result<serde_json::Value, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?.clone();
+    let (puuid, region) = {
+        let t = state.inner.lock().await;
+        (t.puuid.clone(), t.region.clone())
+    };
+    let puuid = puuid.ok_or("No summoner tracked")?;
+    let region = region.ok_or("No region set")?;
+
+    let already_running = sync::is_running();
+    if !already_running {
+        spawn_match_sync(app, state, puuid.clone(), region);
+    }
+    Ok(serde_json::json!({
+        "started": !already_running,
+        "cursor": sync::load_cursor(&puuid),
+    }))
+}
+
+/// Sync cursor and running flag for the tracked player
+#[tauri::command]
+pub async fn get_sync_status() -> Result<serde_json::Value, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let puuid = state.inner.lock().await.puuid.clone().ok_or("No summoner tracked")?;
+    Ok(serde_json::json!({
+        "running": sync::is_running(),
+        "storedMatches": state.client.store().len(),
+        "cursor": sync::load_cursor(&puuid),
+    }))
+}
+
+/// `gameStarted` payload: the spectator game plus past encounters with each lobby member
+#[derive(Serialize, Clone)]
+struct GameStartedPayload {
+    #[serde(flatten)]
+    game: riven::models::spectator_v5::CurrentGameInfo,
+    encounters: Vec<LobbyEncounter>,
+}
+
 #[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 struct MatchPayload {
     game: riven::models::spectator_v5::CurrentGameInfo,
     ranked: Vec<Vec<riven::models::league_v4::LeagueEntry>>,
-    traits: Vec<Vec<String>>,
+    scouting: Vec<PlayerScouting>,
+    premades: Vec<PremadeGroup>,
+    /// Pre-game estimate for the tracked player's team
+    win_probability: Option<WinProbability>,
+    composition: CompositionReport,
 }
 
 /// Detect if player is currently in a live match with robust error handling
@@ -1184,508 +1409,691 @@ async fn get_match_history_with_enhanced_analytics(
     region: &str,
     count: i32,
 ) -> Result<Vec<serde_json::Value>, String> {
-    // Temporarily suppress unused parameter warnings
-    let _ = client;
-    let _ = puuid;
-    let _ = region;
-    // This would integrate with the analytics system
-    // For now, return basic match data
-    
-    let mut matches = Vec::new();
-    
-    // Simplified implementation - would be expanded with full analytics
-    for i in 0..count.min(5) { // Limit for now
-        matches.push(serde_json::json!({
-            "match_id": format!("NA1_{}_{}", chrono::Utc::now().timestamp(), i),
-            "game_creation": chrono::Utc::now().timestamp_millis() - (i as i64 * 86400000), // Days ago
-            "game_duration": 1800 + (i * 300), // Varying game lengths
-            "analytics_calculated": false,
-            "needs_processing": true
-        }));
-    }
-    
-    Ok(matches)
+    let history = client
+        .get_match_history_with_analytics(puuid, region, Some(count))
+        .await
+        .map_err(|e| format!("Failed to get match history: {:?}", e))?;
+
+    // Each entry carries the tracked player's composite score plus the full
+    // ten-player ranking with MVP/ACE flags
+    Ok(history
+        .into_iter()
+        .map(|m| serde_json::to_value(m).unwrap())
+        .collect())
 }
 
 /// Get all champions data (Updated for July 2025)
 #[tauri::command]
 pub async fn get_all_champions() -> Result<Vec<serde_json::Value>, String> {
-    // Comprehensive champion list as of July 2025
-    let champions = vec![
-        serde_json::json!({"id": 266, "name": "Aatrox", "key": "Aatrox", "title": "the Darkin Blade", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 103, "name": "Ahri", "key": "Ahri", "title": "the Nine-Tailed Fox", "roles": ["middle"], "tags": ["Mage", "Assassin"]}),
-        serde_json::json!({"id": 84, "name": "Akali", "key": "Akali", "title": "the Rogue Assassin", "roles": ["middle", "top"], "tags": ["Assassin"]}),
-        serde_json::json!({"id": 166, "name": "Akshan", "key": "Akshan", "title": "the Rogue Sentinel", "roles": ["middle", "top"], "tags": ["Marksman", "Assassin"]}),
-        serde_json::json!({"id": 12, "name": "Alistar", "key": "Alistar", "title": "the Minotaur", "roles": ["support"], "tags": ["Tank", "Support"]}),
-        serde_json::json!({"id": 32, "name": "Amumu", "key": "Amumu", "title": "the Sad Mummy", "roles": ["jungle"], "tags": ["Tank", "Mage"]}),
-        serde_json::json!({"id": 34, "name": "Anivia", "key": "Anivia", "title": "the Cryophoenix", "roles": ["middle"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 1, "name": "Annie", "key": "Annie", "title": "the Dark Child", "roles": ["middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 523, "name": "Aphelios", "key": "Aphelios", "title": "the Weapon of the Faithful", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 22, "name": "Ashe", "key": "Ashe", "title": "the Frost Archer", "roles": ["bottom"], "tags": ["Marksman", "Support"]}),
-        serde_json::json!({"id": 136, "name": "Aurelion Sol", "key": "AurelionSol", "title": "the Star Forger", "roles": ["middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 893, "name": "Aurora", "key": "Aurora", "title": "the Witch Between Worlds", "roles": ["middle", "top"], "tags": ["Mage", "Assassin"]}),
-        serde_json::json!({"id": 268, "name": "Azir", "key": "Azir", "title": "the Emperor of the Sands", "roles": ["middle"], "tags": ["Mage", "Marksman"]}),
-        serde_json::json!({"id": 432, "name": "Bard", "key": "Bard", "title": "the Wandering Caretaker", "roles": ["support"], "tags": ["Support", "Mage"]}),
-        serde_json::json!({"id": 200, "name": "Bel'Veth", "key": "Belveth", "title": "the Empress of the Void", "roles": ["jungle"], "tags": ["Fighter"]}),
-        serde_json::json!({"id": 53, "name": "Blitzcrank", "key": "Blitzcrank", "title": "the Great Steam Golem", "roles": ["support"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 63, "name": "Brand", "key": "Brand", "title": "the Burning Vengeance", "roles": ["support", "middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 201, "name": "Braum", "key": "Braum", "title": "the Heart of the Freljord", "roles": ["support"], "tags": ["Support", "Tank"]}),
-        serde_json::json!({"id": 895, "name": "Briar", "key": "Briar", "title": "the Restrained Hunger", "roles": ["jungle"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 51, "name": "Caitlyn", "key": "Caitlyn", "title": "the Sheriff of Piltover", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 164, "name": "Camille", "key": "Camille", "title": "the Steel Shadow", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 69, "name": "Cassiopeia", "key": "Cassiopeia", "title": "the Serpent's Embrace", "roles": ["middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 31, "name": "Cho'Gath", "key": "Chogath", "title": "the Terror of the Void", "roles": ["top"], "tags": ["Tank", "Mage"]}),
-        serde_json::json!({"id": 42, "name": "Corki", "key": "Corki", "title": "the Daring Bombardier", "roles": ["middle"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 122, "name": "Darius", "key": "Darius", "title": "the Hand of Noxus", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 131, "name": "Diana", "key": "Diana", "title": "Scorn of the Moon", "roles": ["jungle", "middle"], "tags": ["Fighter", "Mage"]}),
-        serde_json::json!({"id": 119, "name": "Draven", "key": "Draven", "title": "the Glorious Executioner", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 36, "name": "Dr. Mundo", "key": "DrMundo", "title": "the Madman of Zaun", "roles": ["top", "jungle"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 245, "name": "Ekko", "key": "Ekko", "title": "the Boy Who Shattered Time", "roles": ["jungle", "middle"], "tags": ["Assassin", "Fighter"]}),
-        serde_json::json!({"id": 60, "name": "Elise", "key": "Elise", "title": "the Spider Queen", "roles": ["jungle"], "tags": ["Mage", "Fighter"]}),
-        serde_json::json!({"id": 28, "name": "Evelynn", "key": "Evelynn", "title": "Agony's Embrace", "roles": ["jungle"], "tags": ["Assassin", "Mage"]}),
-        serde_json::json!({"id": 81, "name": "Ezreal", "key": "Ezreal", "title": "the Prodigal Explorer", "roles": ["bottom"], "tags": ["Marksman", "Mage"]}),
-        serde_json::json!({"id": 9, "name": "Fiddlesticks", "key": "Fiddlesticks", "title": "the Ancient Fear", "roles": ["jungle"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 114, "name": "Fiora", "key": "Fiora", "title": "the Grand Duelist", "roles": ["top"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 105, "name": "Fizz", "key": "Fizz", "title": "the Tidal Trickster", "roles": ["middle"], "tags": ["Assassin", "Fighter"]}),
-        serde_json::json!({"id": 3, "name": "Galio", "key": "Galio", "title": "the Colossus", "roles": ["middle", "support"], "tags": ["Tank", "Mage"]}),
-        serde_json::json!({"id": 41, "name": "Gangplank", "key": "Gangplank", "title": "the Saltwater Scourge", "roles": ["top"], "tags": ["Fighter"]}),
-        serde_json::json!({"id": 86, "name": "Garen", "key": "Garen", "title": "The Might of Demacia", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 150, "name": "Gnar", "key": "Gnar", "title": "the Missing Link", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 79, "name": "Gragas", "key": "Gragas", "title": "the Rabble Rouser", "roles": ["jungle"], "tags": ["Fighter", "Mage"]}),
-        serde_json::json!({"id": 104, "name": "Graves", "key": "Graves", "title": "the Outlaw", "roles": ["jungle"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 887, "name": "Gwen", "key": "Gwen", "title": "The Hallowed Seamstress", "roles": ["top"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 120, "name": "Hecarim", "key": "Hecarim", "title": "the Shadow of War", "roles": ["jungle"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 74, "name": "Heimerdinger", "key": "Heimerdinger", "title": "the Revered Inventor", "roles": ["middle", "support"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 910, "name": "Hwei", "key": "Hwei", "title": "the Visionary", "roles": ["middle", "support"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 420, "name": "Illaoi", "key": "Illaoi", "title": "the Kraken Priestess", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 39, "name": "Irelia", "key": "Irelia", "title": "the Blade Dancer", "roles": ["top", "middle"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 427, "name": "Ivern", "key": "Ivern", "title": "the Green Father", "roles": ["jungle"], "tags": ["Support", "Mage"]}),
-        serde_json::json!({"id": 40, "name": "Janna", "key": "Janna", "title": "the Storm's Fury", "roles": ["support"], "tags": ["Support", "Mage"]}),
-        serde_json::json!({"id": 59, "name": "Jarvan IV", "key": "JarvanIV", "title": "the Exemplar of Demacia", "roles": ["jungle"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 24, "name": "Jax", "key": "Jax", "title": "Grandmaster at Arms", "roles": ["top", "jungle"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 126, "name": "Jayce", "key": "Jayce", "title": "the Defender of Tomorrow", "roles": ["top", "middle"], "tags": ["Fighter", "Marksman"]}),
-        serde_json::json!({"id": 202, "name": "Jhin", "key": "Jhin", "title": "the Virtuoso", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 222, "name": "Jinx", "key": "Jinx", "title": "the Loose Cannon", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 145, "name": "Kai'Sa", "key": "Kaisa", "title": "Daughter of the Void", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 429, "name": "Kalista", "key": "Kalista", "title": "the Spear of Vengeance", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 43, "name": "Karma", "key": "Karma", "title": "the Enlightened One", "roles": ["support", "middle"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 30, "name": "Karthus", "key": "Karthus", "title": "the Deathsinger", "roles": ["jungle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 38, "name": "Kassadin", "key": "Kassadin", "title": "the Void Walker", "roles": ["middle"], "tags": ["Assassin", "Mage"]}),
-        serde_json::json!({"id": 55, "name": "Katarina", "key": "Katarina", "title": "the Sinister Blade", "roles": ["middle"], "tags": ["Assassin", "Mage"]}),
-        serde_json::json!({"id": 10, "name": "Kayle", "key": "Kayle", "title": "the Righteous", "roles": ["top"], "tags": ["Fighter", "Support"]}),
-        serde_json::json!({"id": 141, "name": "Kayn", "key": "Kayn", "title": "the Shadow Reaper", "roles": ["jungle"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 85, "name": "Kennen", "key": "Kennen", "title": "the Heart of the Tempest", "roles": ["top"], "tags": ["Mage", "Marksman"]}),
-        serde_json::json!({"id": 121, "name": "Kha'Zix", "key": "Khazix", "title": "the Voidreaver", "roles": ["jungle"], "tags": ["Assassin"]}),
-        serde_json::json!({"id": 203, "name": "Kindred", "key": "Kindred", "title": "The Eternal Hunters", "roles": ["jungle"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 240, "name": "Kled", "key": "Kled", "title": "the Cantankerous Cavalier", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 96, "name": "Kog'Maw", "key": "KogMaw", "title": "the Mouth of the Abyss", "roles": ["bottom"], "tags": ["Marksman", "Mage"]}),
-        serde_json::json!({"id": 897, "name": "K'Sante", "key": "KSante", "title": "the Pride of Nazumah", "roles": ["top"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 7, "name": "LeBlanc", "key": "Leblanc", "title": "the Deceiver", "roles": ["middle"], "tags": ["Assassin", "Mage"]}),
-        serde_json::json!({"id": 64, "name": "Lee Sin", "key": "LeeSin", "title": "the Blind Monk", "roles": ["jungle"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 89, "name": "Leona", "key": "Leona", "title": "the Radiant Dawn", "roles": ["support"], "tags": ["Tank", "Support"]}),
-        serde_json::json!({"id": 876, "name": "Lillia", "key": "Lillia", "title": "the Bashful Bloom", "roles": ["jungle"], "tags": ["Fighter", "Mage"]}),
-        serde_json::json!({"id": 127, "name": "Lissandra", "key": "Lissandra", "title": "the Ice Witch", "roles": ["middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 236, "name": "Lucian", "key": "Lucian", "title": "the Purifier", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 117, "name": "Lulu", "key": "Lulu", "title": "the Fae Sorceress", "roles": ["support"], "tags": ["Support", "Mage"]}),
-        serde_json::json!({"id": 99, "name": "Lux", "key": "Lux", "title": "the Lady of Luminosity", "roles": ["support", "middle"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 54, "name": "Malphite", "key": "Malphite", "title": "Shard of the Monolith", "roles": ["top"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 90, "name": "Malzahar", "key": "Malzahar", "title": "the Prophet of the Void", "roles": ["middle"], "tags": ["Mage", "Assassin"]}),
-        serde_json::json!({"id": 57, "name": "Maokai", "key": "Maokai", "title": "the Twisted Treant", "roles": ["support", "jungle"], "tags": ["Tank", "Mage"]}),
-        serde_json::json!({"id": 11, "name": "Master Yi", "key": "MasterYi", "title": "the Wuju Bladesman", "roles": ["jungle"], "tags": ["Assassin", "Fighter"]}),
-        serde_json::json!({"id": 902, "name": "Milio", "key": "Milio", "title": "the Gentle Flame", "roles": ["support"], "tags": ["Support"]}),
-        serde_json::json!({"id": 21, "name": "Miss Fortune", "key": "MissFortune", "title": "the Bounty Hunter", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 62, "name": "Wukong", "key": "MonkeyKing", "title": "the Monkey King", "roles": ["top", "jungle"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 82, "name": "Mordekaiser", "key": "Mordekaiser", "title": "the Iron Revenant", "roles": ["top"], "tags": ["Fighter"]}),
-        serde_json::json!({"id": 25, "name": "Morgana", "key": "Morgana", "title": "the Fallen", "roles": ["support"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 267, "name": "Nami", "key": "Nami", "title": "the Tidecaller", "roles": ["support"], "tags": ["Support", "Mage"]}),
-        serde_json::json!({"id": 75, "name": "Nasus", "key": "Nasus", "title": "the Curator of the Sands", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 111, "name": "Nautilus", "key": "Nautilus", "title": "the Titan of the Depths", "roles": ["support"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 518, "name": "Neeko", "key": "Neeko", "title": "the Curious Chameleon", "roles": ["middle", "support"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 76, "name": "Nidalee", "key": "Nidalee", "title": "the Bestial Huntress", "roles": ["jungle"], "tags": ["Assassin", "Mage"]}),
-        serde_json::json!({"id": 895, "name": "Nilah", "key": "Nilah", "title": "the Joy Unbound", "roles": ["bottom"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 56, "name": "Nocturne", "key": "Nocturne", "title": "the Eternal Nightmare", "roles": ["jungle"], "tags": ["Assassin", "Fighter"]}),
-        serde_json::json!({"id": 20, "name": "Nunu & Willump", "key": "Nunu", "title": "the Boy and His Yeti", "roles": ["jungle"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 2, "name": "Olaf", "key": "Olaf", "title": "the Berserker", "roles": ["top", "jungle"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 61, "name": "Orianna", "key": "Orianna", "title": "the Lady of Clockwork", "roles": ["middle"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 516, "name": "Ornn", "key": "Ornn", "title": "The Fire Beneath the Mountain", "roles": ["top"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 80, "name": "Pantheon", "key": "Pantheon", "title": "the Unbreakable Spear", "roles": ["middle", "support"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 78, "name": "Poppy", "key": "Poppy", "title": "Keeper of the Hammer", "roles": ["jungle", "top"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 555, "name": "Pyke", "key": "Pyke", "title": "the Bloodharbor Ripper", "roles": ["support"], "tags": ["Support", "Assassin"]}),
-        serde_json::json!({"id": 246, "name": "Qiyana", "key": "Qiyana", "title": "Empress of the Elements", "roles": ["middle"], "tags": ["Assassin", "Fighter"]}),
-        serde_json::json!({"id": 133, "name": "Quinn", "key": "Quinn", "title": "Demacia's Wings", "roles": ["top"], "tags": ["Marksman", "Assassin"]}),
-        serde_json::json!({"id": 497, "name": "Rakan", "key": "Rakan", "title": "The Charmer", "roles": ["support"], "tags": ["Support"]}),
-        serde_json::json!({"id": 33, "name": "Rammus", "key": "Rammus", "title": "the Armordillo", "roles": ["jungle"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 421, "name": "Rek'Sai", "key": "RekSai", "title": "the Void Burrower", "roles": ["jungle"], "tags": ["Fighter"]}),
-        serde_json::json!({"id": 526, "name": "Rell", "key": "Rell", "title": "the Iron Maiden", "roles": ["support"], "tags": ["Tank", "Support"]}),
-        serde_json::json!({"id": 888, "name": "Renata Glasc", "key": "Renata", "title": "the Chem-Baroness", "roles": ["support"], "tags": ["Support", "Mage"]}),
-        serde_json::json!({"id": 58, "name": "Renekton", "key": "Renekton", "title": "the Butcher of the Sands", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 107, "name": "Rengar", "key": "Rengar", "title": "the Pridestalker", "roles": ["jungle"], "tags": ["Assassin", "Fighter"]}),
-        serde_json::json!({"id": 92, "name": "Riven", "key": "Riven", "title": "the Exile", "roles": ["top"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 68, "name": "Rumble", "key": "Rumble", "title": "the Mechanized Menace", "roles": ["top"], "tags": ["Fighter", "Mage"]}),
-        serde_json::json!({"id": 13, "name": "Ryze", "key": "Ryze", "title": "the Rune Mage", "roles": ["middle"], "tags": ["Mage", "Fighter"]}),
-        serde_json::json!({"id": 360, "name": "Samira", "key": "Samira", "title": "the Desert Rose", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 113, "name": "Sejuani", "key": "Sejuani", "title": "Fury of the North", "roles": ["jungle"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 235, "name": "Senna", "key": "Senna", "title": "the Redeemer", "roles": ["support", "bottom"], "tags": ["Marksman", "Support"]}),
-        serde_json::json!({"id": 147, "name": "Seraphine", "key": "Seraphine", "title": "the Starry-Eyed Songstress", "roles": ["support", "middle"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 875, "name": "Sett", "key": "Sett", "title": "the Boss", "roles": ["top", "support"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 35, "name": "Shaco", "key": "Shaco", "title": "the Demon Jester", "roles": ["jungle"], "tags": ["Assassin"]}),
-        serde_json::json!({"id": 98, "name": "Shen", "key": "Shen", "title": "the Eye of Twilight", "roles": ["top"], "tags": ["Tank"]}),
-        serde_json::json!({"id": 102, "name": "Shyvana", "key": "Shyvana", "title": "the Half-Dragon", "roles": ["jungle"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 27, "name": "Singed", "key": "Singed", "title": "the Mad Chemist", "roles": ["top"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 14, "name": "Sion", "key": "Sion", "title": "The Undead Juggernaut", "roles": ["top"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 15, "name": "Sivir", "key": "Sivir", "title": "the Battle Mistress", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 72, "name": "Skarner", "key": "Skarner", "title": "the Crystal Vanguard", "roles": ["jungle"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 901, "name": "Smolder", "key": "Smolder", "title": "the Fiery Fledgling", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 37, "name": "Sona", "key": "Sona", "title": "Maven of the Strings", "roles": ["support"], "tags": ["Support", "Mage"]}),
-        serde_json::json!({"id": 16, "name": "Soraka", "key": "Soraka", "title": "the Starchild", "roles": ["support"], "tags": ["Support", "Mage"]}),
-        serde_json::json!({"id": 50, "name": "Swain", "key": "Swain", "title": "the Noxian Grand General", "roles": ["middle", "support"], "tags": ["Mage", "Fighter"]}),
-        serde_json::json!({"id": 517, "name": "Sylas", "key": "Sylas", "title": "the Unshackled", "roles": ["middle", "jungle"], "tags": ["Mage", "Assassin"]}),
-        serde_json::json!({"id": 134, "name": "Syndra", "key": "Syndra", "title": "the Dark Sovereign", "roles": ["middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 223, "name": "Tahm Kench", "key": "TahmKench", "title": "the River King", "roles": ["support", "top"], "tags": ["Support", "Tank"]}),
-        serde_json::json!({"id": 163, "name": "Taliyah", "key": "Taliyah", "title": "the Stoneweaver", "roles": ["jungle", "middle"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 91, "name": "Talon", "key": "Talon", "title": "the Blade's Shadow", "roles": ["middle"], "tags": ["Assassin"]}),
-        serde_json::json!({"id": 44, "name": "Taric", "key": "Taric", "title": "the Shield of Valoran", "roles": ["support"], "tags": ["Support", "Fighter"]}),
-        serde_json::json!({"id": 17, "name": "Teemo", "key": "Teemo", "title": "the Swift Scout", "roles": ["top"], "tags": ["Marksman", "Assassin"]}),
-        serde_json::json!({"id": 412, "name": "Thresh", "key": "Thresh", "title": "the Chain Warden", "roles": ["support"], "tags": ["Support", "Fighter"]}),
-        serde_json::json!({"id": 18, "name": "Tristana", "key": "Tristana", "title": "the Yordle Gunner", "roles": ["bottom"], "tags": ["Marksman", "Assassin"]}),
-        serde_json::json!({"id": 48, "name": "Trundle", "key": "Trundle", "title": "the Troll King", "roles": ["top", "jungle"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 23, "name": "Tryndamere", "key": "Tryndamere", "title": "the Barbarian King", "roles": ["top"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 4, "name": "Twisted Fate", "key": "TwistedFate", "title": "the Card Master", "roles": ["middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 29, "name": "Twitch", "key": "Twitch", "title": "the Plague Rat", "roles": ["bottom"], "tags": ["Marksman", "Assassin"]}),
-        serde_json::json!({"id": 77, "name": "Udyr", "key": "Udyr", "title": "the Spirit Walker", "roles": ["jungle"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 6, "name": "Urgot", "key": "Urgot", "title": "the Dreadnought", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 110, "name": "Varus", "key": "Varus", "title": "the Arrow of Retribution", "roles": ["bottom"], "tags": ["Marksman", "Mage"]}),
-        serde_json::json!({"id": 67, "name": "Vayne", "key": "Vayne", "title": "the Night Hunter", "roles": ["bottom"], "tags": ["Marksman", "Assassin"]}),
-        serde_json::json!({"id": 45, "name": "Veigar", "key": "Veigar", "title": "the Tiny Master of Evil", "roles": ["middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 161, "name": "Vel'Koz", "key": "Velkoz", "title": "the Eye of the Void", "roles": ["middle", "support"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 711, "name": "Vex", "key": "Vex", "title": "the Gloomist", "roles": ["middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 254, "name": "Vi", "key": "Vi", "title": "the Piltover Enforcer", "roles": ["jungle"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 234, "name": "Viego", "key": "Viego", "title": "The Ruined King", "roles": ["jungle"], "tags": ["Assassin", "Fighter"]}),
-        serde_json::json!({"id": 112, "name": "Viktor", "key": "Viktor", "title": "the Machine Herald", "roles": ["middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 8, "name": "Vladimir", "key": "Vladimir", "title": "the Crimson Reaper", "roles": ["middle", "top"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 106, "name": "Volibear", "key": "Volibear", "title": "the Relentless Storm", "roles": ["jungle", "top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 19, "name": "Warwick", "key": "Warwick", "title": "the Uncaged Wrath of Zaun", "roles": ["jungle"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 498, "name": "Xayah", "key": "Xayah", "title": "the Rebel", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 101, "name": "Xerath", "key": "Xerath", "title": "the Magus Ascendant", "roles": ["middle", "support"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 5, "name": "Xin Zhao", "key": "XinZhao", "title": "the Seneschal of Demacia", "roles": ["jungle"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 115, "name": "Yasuo", "key": "Yasuo", "title": "the Unforgiven", "roles": ["middle", "top"], "tags": ["Fighter", "Assassin"]}),
-        serde_json::json!({"id": 777, "name": "Yone", "key": "Yone", "title": "the Unforgotten", "roles": ["middle", "top"], "tags": ["Assassin", "Fighter"]}),
-        serde_json::json!({"id": 83, "name": "Yorick", "key": "Yorick", "title": "Shepherd of Souls", "roles": ["top"], "tags": ["Fighter", "Tank"]}),
-        serde_json::json!({"id": 350, "name": "Yuumi", "key": "Yuumi", "title": "the Magical Cat", "roles": ["support"], "tags": ["Support", "Mage"]}),
-        serde_json::json!({"id": 154, "name": "Zac", "key": "Zac", "title": "the Secret Weapon", "roles": ["jungle"], "tags": ["Tank", "Fighter"]}),
-        serde_json::json!({"id": 238, "name": "Zed", "key": "Zed", "title": "the Master of Shadows", "roles": ["middle"], "tags": ["Assassin"]}),
-        serde_json::json!({"id": 221, "name": "Zeri", "key": "Zeri", "title": "The Spark of Zaun", "roles": ["bottom"], "tags": ["Marksman"]}),
-        serde_json::json!({"id": 115, "name": "Ziggs", "key": "Ziggs", "title": "the Hexplosives Expert", "roles": ["middle"], "tags": ["Mage"]}),
-        serde_json::json!({"id": 26, "name": "Zilean", "key": "Zilean", "title": "the Chronokeeper", "roles": ["support"], "tags": ["Support", "Mage"]}),
-        serde_json::json!({"id": 142, "name": "Zoe", "key": "Zoe", "title": "the Aspect of Twilight", "roles": ["middle"], "tags": ["Mage", "Support"]}),
-        serde_json::json!({"id": 143, "name": "Zyra", "key": "Zyra", "title": "Rise of the Thorns", "roles": ["support"], "tags": ["Mage", "Support"]})
-    ];
-    Ok(champions)
-}
-
-/// Get champion builds for a specific champion and role
+    Ok(CHAMPIONS.iter().map(|c| c.to_json()).collect())
+}
+
+/// Most recent games per champion to pull timelines for when aggregating
+const CHAMPION_TIMELINE_PREFETCH: usize = 20;
+
+/// Make sure the tracked player's recent games on a champion have timelines stored
+async fn prefetch_champion_timelines(client: &RiotClient, puuid: &str, region: &str, champion_id: i32) {
+    use log::warn;
+    let match_ids: Vec<String> = client
+        .store()
+        .matches_for(puuid)
+        .into_iter()
+        .filter(|m| m.info.participants.iter()
+            .any(|p| p.puuid == puuid && champion_id_of(p) == champion_id))
+        .take(CHAMPION_TIMELINE_PREFETCH)
+        .map(|m| m.metadata.match_id)
+        .collect();
+
+    for match_id in match_ids {
+        if let Err(e) = client.get_timeline_cached(&match_id, region).await {
+            warn!("Failed to fetch timeline for {}: {:?}", match_id, e);
+        }
+    }
+}
+
+/// Get champion builds for a specific champion and role, aggregated from stored matches
 #[tauri::command]
-pub async fn get_champion_builds(_champion_id: i32, _role: String, rank: Option<String>) -> Result<Vec<serde_json::Value>, String> {
-    let _rank = rank.unwrap_or_else(|| "diamond+".to_string());
-    
-    // Mock build data - in production this would come from external APIs like op.gg, u.gg, etc.
-    let builds = vec![
-        serde_json::json!({
-            "id": "meta_build_1",
-            "name": "Meta Build",
-            "source": "Community",
-            "pickRate": 67.8,
-            "winRate": 52.4,
-            "games": 12450,
-            "items": {
-                "core": [3074, 3071, 3156], // Ravenous Hydra, Black Cleaver, Maw of Malmortius
-                "boots": [3047], // Plated Steelcaps
-                "situational": [3193, 3026, 3065, 3053]
-            },
-            "runes": {
-                "primary": {
-                    "tree": "Precision",
-                    "keystone": "Conqueror",
-                    "runes": ["Triumph", "Legend: Alacrity", "Last Stand"]
-                },
-                "secondary": {
-                    "tree": "Resolve",
-                    "runes": ["Second Wind", "Unflinching"]
-                },
-                "shards": ["Adaptive Force", "Adaptive Force", "Health"]
-            },
-            "skillOrder": "Q>E>W",
-            "startingItems": [1054, 2003], // Doran's Shield, Health Potion
-            "summonerSpells": ["Flash", "Teleport"]
-        }),
-        serde_json::json!({
-            "id": "aggressive_build_1",
-            "name": "Aggressive Build",
-            "source": "Pro Play",
-            "pickRate": 23.1,
-            "winRate": 54.7,
-            "games": 4230,
-            "items": {
-                "core": [3074, 3071, 3812], // Ravenous Hydra, Black Cleaver, Death's Dance
-                "boots": [3006], // Berserker's Greaves
-                "situational": [3156, 3053, 3026]
-            },
-            "runes": {
-                "primary": {
-                    "tree": "Precision",
-                    "keystone": "Conqueror",
-                    "runes": ["Triumph", "Legend: Bloodline", "Coup de Grace"]
-                },
-                "secondary": {
-                    "tree": "Domination",
-                    "runes": ["Sudden Impact", "Ravenous Hunter"]
-                },
-                "shards": ["Adaptive Force", "Adaptive Force", "Health"]
-            },
-            "skillOrder": "Q>W>E",
-            "startingItems": [1055, 2003], // Doran's Blade, Health Potion
-            "summonerSpells": ["Flash", "Ignite"]
-        })
-    ];
-    
+pub async fn get_champion_builds(champion_id: i32, role: String, rank: Option<String>) -> Result<Vec<serde_json::Value>, String> {
+    let builds = collect_champion_builds(champion_id, &role, rank).await?;
+    Ok(builds
+        .into_iter()
+        .map(|b| serde_json::to_value(b).unwrap())
+        .collect())
+}
+
+/// The player's own builds followed by corpus-wide builds for a champion and role
+async fn collect_champion_builds(champion_id: i32, role: &str, rank: Option<String>) -> Result<Vec<ChampionBuild>, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?.clone();
+    let rank = rank.unwrap_or_else(|| "all".to_string());
+    let (puuid, region) = {
+        let t = state.inner.lock().await;
+        (t.puuid.clone(), t.region.clone())
+    };
+
+    if let (Some(puuid), Some(region)) = (&puuid, &region) {
+        prefetch_champion_timelines(&state.client, puuid, region, champion_id).await;
+    }
+
+    let stored = state.client.store().stored_in_bucket(RankFilter::parse(Some(&rank)));
+    let query = BuildQuery {
+        champion_id,
+        role: Some(role).filter(|r| !r.is_empty()),
+        rank: &rank,
+    };
+
+    let items = current_item_data().await;
+
+    let mut builds = Vec::new();
+    if let Some(puuid) = &puuid {
+        builds.extend(aggregate_builds(&stored, &query, items.as_ref(), Some(puuid), "player", "Your Games"));
+    }
+    builds.extend(aggregate_builds(&stored, &query, items.as_ref(), None, "corpus", "Local Match Corpus"));
     Ok(builds)
 }
 
-/// Get champion matchups for a specific champion and role
+/// Export a champion's builds as League client item sets, into `directory` or the detected
+/// League install. Returns the files written.
 #[tauri::command]
-pub async fn get_champion_matchups(_champion_id: i32, _role: String, rank: Option<String>) -> Result<Vec<serde_json::Value>, String> {
-    let _rank = rank.unwrap_or_else(|| "diamond+".to_string());
-    
-    // Mock matchup data
-    let matchups = vec![
-        serde_json::json!({
-            "enemyChampionId": 54, // Malphite
-            "enemyChampionName": "Malphite",
-            "difficulty": "Hard",
-            "winRate": 42.3,
-            "games": 1850,
-            "goldDiff15": -287,
-            "csDiff15": -12,
-            "tips": [
-                "Rush Blade of the Ruined King for % health damage",
-                "Take short trades when his passive is down",
-                "Avoid all-ins when he has ultimate"
-            ],
-            "counters": ["Build MR early", "Take Fleet Footwork", "Consider Hexdrinker"]
-        }),
-        serde_json::json!({
-            "enemyChampionId": 92, // Riven
-            "enemyChampionName": "Riven",
-            "difficulty": "Easy",
-            "winRate": 58.7,
-            "games": 2340,
-            "goldDiff15": 156,
-            "csDiff15": 8,
-            "tips": [
-                "Trade when her abilities are on cooldown",
-                "Use Q to interrupt her combos",
-                "Build armor early"
-            ],
-            "counters": ["Bramble Vest rush", "Play around cooldowns", "Don't chase"]
-        }),
-        serde_json::json!({
-            "enemyChampionId": 122, // Darius
-            "enemyChampionName": "Darius",
-            "difficulty": "Medium",
-            "winRate": 49.8,
-            "games": 3120,
-            "goldDiff15": -45,
-            "csDiff15": -2,
-            "tips": [
-                "Respect his level 6 power spike",
-                "Don't fight when he has 5 stacks",
-                "Use range advantage with Q"
-            ],
-            "counters": ["Kite with Q", "Build healing reduction", "Avoid extended trades"]
-        })
-    ];
-    
-    Ok(matchups)
+pub async fn export_item_sets(
+    champion_id: i32,
+    role: String,
+    rank: Option<String>,
+    directory: Option<String>,
+) -> Result<Vec<String>, String> {
+    let champion = champion_by_id(champion_id).ok_or_else(|| format!("Unknown champion {}", champion_id))?;
+    let base = match directory.filter(|d| !d.trim().is_empty()) {
+        Some(dir) => std::path::PathBuf::from(dir),
+        None => detect_league_install().ok_or("League install not found; choose a directory to export to")?,
+    };
+    let builds = collect_champion_builds(champion_id, &role, rank).await?;
+    if builds.is_empty() {
+        return Err(format!("No builds available for {}", champion.name));
+    }
+    let written = write_item_sets(&base, champion.key, champion_id, &role, &builds)?;
+    Ok(written.iter().map(|p| p.display().to_string()).collect())
+}
+
+/// Get champion matchups for a specific champion and role, computed from lane pairings in stored matches
+#[tauri::command]
+pub async fn get_champion_matchups(champion_id: i32, role: String, rank: Option<String>) -> Result<Vec<serde_json::Value>, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?.clone();
+    let (puuid, region) = {
+        let t = state.inner.lock().await;
+        (t.puuid.clone(), t.region.clone())
+    };
+
+    if let (Some(puuid), Some(region)) = (&puuid, &region) {
+        prefetch_champion_timelines(&state.client, puuid, region, champion_id).await;
+    }
+
+    let stored = state.client.store().stored_in_bucket(RankFilter::parse(rank.as_deref()));
+    let role = Some(role.as_str()).filter(|r| !r.is_empty());
+
+    Ok(aggregate_matchups(&stored, champion_id, role)
+        .into_iter()
+        .map(|m| serde_json::to_value(m).unwrap())
+        .collect())
 }
 
+/// Ban recommendations returned by `get_counter_data`
+const MAX_BAN_RECOMMENDATIONS: usize = 3;
+
 /// Get counter data (champions that counter this champion and champions this champion counters)
 #[tauri::command]
 pub async fn get_counter_data(champion_id: i32, role: String, rank: Option<String>) -> Result<serde_json::Value, String> {
-    let _rank = rank.unwrap_or_else(|| "diamond+".to_string());
-    
-    // Mock counter data
-    let counter_data = serde_json::json!({
+    let state = APP_STATE.get().ok_or("not initialized")?.clone();
+    let stored = state.client.store().stored_in_bucket(RankFilter::parse(rank.as_deref()));
+    let matchups = aggregate_matchups(&stored, champion_id, Some(role.as_str()).filter(|r| !r.is_empty()));
+    let (counters, good_against) = classify_counters(&matchups);
+
+    let bans = ban_rates(&stored);
+    let ban_recommendations: Vec<serde_json::Value> = counters.hard_counters.iter()
+        .chain(counters.soft_counters.iter())
+        .take(MAX_BAN_RECOMMENDATIONS)
+        .map(|c| serde_json::json!({
+            "championId": c.champion_id,
+            "championName": c.champion_name,
+            "banRate": bans.get(&c.champion_id).copied().unwrap_or(0.0),
+            "reason": c.reason,
+        }))
+        .collect();
+
+    Ok(serde_json::json!({
         "championId": champion_id,
         "role": role,
-        "counters": {
-            "hardCounters": [
-                {
-                    "championId": 54,
-                    "championName": "Malphite",
-                    "winRateAgainst": 42.3,
-                    "difficulty": "Hard",
-                    "reason": "Tank with heavy armor and CC"
-                },
-                {
-                    "championId": 57,
-                    "championName": "Maokai", 
-                    "winRateAgainst": 44.1,
-                    "difficulty": "Hard",
-                    "reason": "Sustain and crowd control"
-                }
-            ],
-            "softCounters": [
-                {
-                    "championId": 17,
-                    "championName": "Teemo",
-                    "winRateAgainst": 46.8,
-                    "difficulty": "Medium",
-                    "reason": "Range advantage and blind"
-                },
-                {
-                    "championId": 85,
-                    "championName": "Kennen",
-                    "winRateAgainst": 47.2,
-                    "difficulty": "Medium", 
-                    "reason": "Range and escape tools"
-                }
-            ]
-        },
-        "goodAgainst": {
-            "hardCounters": [
-                {
-                    "championId": 92,
-                    "championName": "Riven",
-                    "winRateAgainst": 58.7,
-                    "difficulty": "Easy",
-                    "reason": "Can interrupt her combos and sustain"
-                },
-                {
-                    "championId": 114,
-                    "championName": "Fiora",
-                    "winRateAgainst": 56.4,
-                    "difficulty": "Easy",
-                    "reason": "Good sustain and can match split push"
-                }
-            ],
-            "softCounters": [
-                {
-                    "championId": 24,
-                    "championName": "Jax",
-                    "winRateAgainst": 53.2,
-                    "difficulty": "Medium",
-                    "reason": "Better early game presence"
-                }
-            ]
-        },
-        "banRecommendations": [
-            {
-                "championId": 54,
-                "championName": "Malphite",
-                "banRate": 15.2,
-                "reason": "Hardest counter in current meta"
-            },
-            {
-                "championId": 122,
-                "championName": "Darius", 
-                "banRate": 12.8,
-                "reason": "Popular pick with good matchup"
-            }
-        ]
-    });
-    
-    Ok(counter_data)
+        "minimumGames": MIN_MATCHUP_GAMES,
+        "counters": counters,
+        "goodAgainst": good_against,
+        "banRecommendations": ban_recommendations,
+    }))
 }
 
-/// Get detailed champion statistics including user performance
+/// Get detailed champion statistics for the tracked player, aggregated from stored matches
 #[tauri::command]
 pub async fn get_detailed_champion_stats(champion_id: i32) -> Result<serde_json::Value, String> {
+    use log::warn;
     let state = APP_STATE.get().ok_or("App state not initialized")?;
     
-    let (_puuid, _region) = {
+    let (puuid, region) = {
         let state_guard = state.inner.lock().await;
         (state_guard.puuid.clone(), state_guard.region.clone())
     };
-    
-    // Mock detailed stats - in production would aggregate from match history
-    let stats = serde_json::json!({
-        "championId": champion_id,
-        "championName": "Aatrox", // Would be dynamically determined
-        "userStats": {
-            "gamesPlayed": 47,
-            "wins": 29,
-            "losses": 18,
-            "winRate": 61.7,
-            "kda": {
-                "kills": 8.2,
-                "deaths": 5.1,
-                "assists": 6.8,
-                "ratio": 2.94
-            },
-            "averageStats": {
-                "cs": 156.3,
-                "csPerMinute": 5.5,
-                "gold": 12450,
-                "damage": 23890,
-                "damageToChampions": 18340,
-                "visionScore": 18.2,
-                "gameLength": 28.5
-            },
-            "masteryInfo": {
-                "level": 7,
-                "points": 234567,
-                "tokensEarned": 0,
-                "chestGranted": true
-            },
-            "recentForm": {
-                "last10Games": {
-                    "wins": 7,
-                    "losses": 3,
-                    "winRate": 70.0
-                },
-                "trend": "improving"
-            }
-        },
-        "globalStats": {
-            "pickRate": 8.2,
-            "banRate": 12.5,
-            "winRate": 51.8,
-            "tier": "S",
-            "rank": 15
-        },
-        "rolePerformance": {
-            "top": {
-                "gamesPlayed": 43,
-                "winRate": 65.1,
-                "kda": 3.0,
-                "primaryRole": true
-            },
-            "middle": {
-                "gamesPlayed": 4,
-                "winRate": 25.0,
-                "kda": 2.26,
-                "primaryRole": false
-            }
-        },
-        "itemStats": {
-            "mostBuilt": [
-                {"itemId": 3074, "itemName": "Ravenous Hydra", "buildRate": 78.2, "winRate": 54.1},
-                {"itemId": 3071, "itemName": "Black Cleaver", "buildRate": 65.8, "winRate": 52.8},
-                {"itemId": 3156, "itemName": "Maw of Malmortius", "buildRate": 45.2, "winRate": 56.3}
-            ],
-            "highestWinRate": [
-                {"itemId": 3812, "itemName": "Death's Dance", "buildRate": 35.1, "winRate": 58.7},
-                {"itemId": 3156, "itemName": "Maw of Malmortius", "buildRate": 45.2, "winRate": 56.3}
-            ]
-        },
-        "skillOrder": {
-            "mostPopular": "Q>E>W",
-            "highestWinRate": "Q>W>E",
-            "userPreference": "Q>E>W"
+    let puuid = puuid.ok_or("No summoner tracked")?;
+    let region = region.ok_or("No region set")?;
+
+    prefetch_champion_timelines(&state.client, &puuid, &region, champion_id).await;
+
+    let mastery = match state.client.get_champion_mastery(&puuid, champion_id, &region).await {
+        Ok(mastery) => mastery.map(|m| MasteryInfo {
+            level: m.champion_level,
+            points: m.champion_points,
+            tokens_earned: m.tokens_earned,
+        }),
+        Err(e) => {
+            warn!("Failed to fetch champion mastery for {}: {:?}", champion_id, e);
+            None
         }
+    };
+
+    let corpus = state.client.store().stored_in_bucket(RankFilter::All);
+    match compute_champion_stats(&corpus, &puuid, champion_id, mastery) {
+        Some(stats) => serde_json::to_value(stats).map_err(|e| e.to_string()),
+        None => Err(format!("No stored games on champion {}", champion_id)),
+    }
+}
+
+/// Frequent duo partners and recurring opponents from the tracked player's stored matches
+#[tauri::command]
+pub async fn get_duo_synergy() -> Result<serde_json::Value, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let puuid = state.inner.lock().await.puuid.clone().ok_or("No summoner tracked")?;
+
+    let history = state.client.stored_history(&puuid);
+    serde_json::to_value(analyze_synergy(&history, &puuid)).map_err(|e| e.to_string())
+}
+
+/// Play sessions, post-loss performance and the current tilt assessment for the tracked player
+#[tauri::command]
+pub async fn get_session_analysis() -> Result<serde_json::Value, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let puuid = state.inner.lock().await.puuid.clone().ok_or("No summoner tracked")?;
+
+    let history = state.client.stored_history(&puuid);
+    serde_json::to_value(analyze_sessions(&history, &puuid)).map_err(|e| e.to_string())
+}
+
+/// Win rate, performance and game length by local hour of day and weekday
+#[tauri::command]
+pub async fn get_playtime_heatmap() -> Result<serde_json::Value, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let puuid = state.inner.lock().await.puuid.clone().ok_or("No summoner tracked")?;
+
+    let history = state.client.stored_history(&puuid);
+    serde_json::to_value(playtime_heatmap(&history)).map_err(|e| e.to_string())
+}
+
+/// Rolling-window trends, patch change points and adaptation for the tracked player
+#[tauri::command]
+pub async fn get_performance_trends() -> Result<serde_json::Value, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let puuid = state.inner.lock().await.puuid.clone().ok_or("No summoner tracked")?;
+
+    let matches = state.client.store().matches_for(&puuid);
+    serde_json::to_value(analyze_trends(&matches, &puuid)).map_err(|e| e.to_string())
+}
+
+/// Prioritised, role-specific focus areas from the tracked player's recent stored games
+#[tauri::command]
+pub async fn get_recommendations() -> Result<Vec<serde_json::Value>, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let puuid = tracked_puuid().await?;
+
+    Ok(recommend(&state.client.stored_history(&puuid))
+        .into_iter()
+        .map(|r| serde_json::to_value(r).unwrap())
+        .collect())
+}
+
+/// Default number of recent games fetched per player for a comparison
+const COMPARISON_SAMPLE: i32 = 20;
+
+/// Resolve "Name#TAG" to a puuid; an empty id or "tracked" means the tracked player
+async fn resolve_riot_id(client: &RiotClient, riot_id: &str, region: &str) -> Result<(String, String), String> {
+    if riot_id.is_empty() || riot_id.eq_ignore_ascii_case("tracked") {
+        let state = APP_STATE.get().ok_or("not initialized")?;
+        let t = state.inner.lock().await;
+        let puuid = t.puuid.clone().ok_or("No summoner tracked")?;
+        return Ok((t.name.clone().unwrap_or_default(), puuid));
+    }
+    let (game_name, tag_line) = riot_id.split_once('#')
+        .ok_or_else(|| format!("Expected a Riot ID like Name#TAG, got {}", riot_id))?;
+    match client.get_account_by_riot_id_with_retry(game_name, tag_line, region).await {
+        Ok(Some(account)) => Ok((riot_id.to_string(), account.puuid)),
+        Ok(None) => Err(format!("Account {} not found", riot_id)),
+        Err(e) => Err(format!("Failed to look up {}: {}", riot_id, e)),
+    }
+}
+
+/// Rank, mastery pool, traits and filtered match sample for one side of a comparison
+async fn comparison_side(
+    client: &RiotClient,
+    riot_id: &str,
+    puuid: &str,
+    region: &str,
+    count: i32,
+) -> PlayerSources {
+    use log::warn;
+    let ranked = client.get_ranked_stats_with_retry(puuid, region).await.unwrap_or_else(|e| {
+        warn!("Failed to get ranked stats for {}: {}", riot_id, e);
+        Vec::new()
     });
-    
-    Ok(stats)
+    let masteries = client.get_champion_masteries_with_retry(puuid, region).await.unwrap_or_else(|e| {
+        warn!("Failed to get masteries for {}: {}", riot_id, e);
+        Vec::new()
+    });
+    let history = client.get_match_history_with_analytics(puuid, region, Some(count)).await.unwrap_or_else(|e| {
+        warn!("Failed to get match history for {}: {:?}", riot_id, e);
+        Vec::new()
+    });
+    PlayerSources { ranked, masteries, history }
+}
+
+/// Compare two players side by side over an equal, optionally queue-filtered sample.
+/// Either Riot ID may be "tracked" to use the tracked account.
+#[tauri::command]
+pub async fn compare_players(
+    first: String,
+    second: String,
+    region: Option<String>,
+    queue_id: Option<i32>,
+    count: Option<i32>,
+) -> Result<serde_json::Value, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?.clone();
+    let region = match region {
+        Some(region) => region,
+        None => state.inner.lock().await.region.clone().ok_or("No region set")?,
+    };
+    let count = count.unwrap_or(COMPARISON_SAMPLE);
+    let client = &state.client;
+
+    let (first_id, first_puuid) = resolve_riot_id(client, &first, &region).await?;
+    let (second_id, second_puuid) = resolve_riot_id(client, &second, &region).await?;
+
+    let (first_side, second_side) = futures::future::join(
+        comparison_side(client, &first_id, &first_puuid, &region, count),
+        comparison_side(client, &second_id, &second_puuid, &region, count),
+    ).await;
+    let (first_sample, second_sample) = equalize_samples(first_side.history, second_side.history, queue_id);
+    let champion_names = fetch_champion_map().await.unwrap_or_default();
+
+    let report = HeadToHead {
+        first: summarize_player(
+            &first_id, &first_puuid, &first_side.ranked, &first_side.masteries,
+            &champion_names, &first_sample,
+        ),
+        second: summarize_player(
+            &second_id, &second_puuid, &second_side.ranked, &second_side.masteries,
+            &champion_names, &second_sample,
+        ),
+        shared: shared_games(&client.store().matches_for(&first_puuid), &first_puuid, &second_puuid),
+    };
+    serde_json::to_value(report).map_err(|e| e.to_string())
+}
+
+/// Damage split, frontline, engage, poke, scaling and CC profiles for both teams of a stored match
+#[tauri::command]
+pub async fn get_match_composition(match_id: String) -> Result<CompositionReport, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let m = state.client.store().get(&match_id)
+        .ok_or_else(|| format!("Match {} is not stored", match_id))?;
+    Ok(match_composition(&m))
+}
+
+/// The tracked player's win rate by ally and enemy composition category
+#[tauri::command]
+pub async fn get_composition_win_rates() -> Result<Vec<CompositionWinRate>, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let puuid = tracked_puuid().await?;
+    Ok(composition_win_rates(&state.client.store().matches_for(&puuid), &puuid))
+}
+
+/// Pick suggestions for the assigned role during champion select, from the player's pool,
+/// stored matchup results against the enemy picks and the ally team's composition gaps
+#[tauri::command]
+pub async fn get_draft_suggestions(
+    role: String,
+    ally_picks: Vec<i32>,
+    enemy_picks: Vec<i32>,
+    bans: Vec<i32>,
+) -> Result<Vec<DraftSuggestion>, String> {
+    use log::warn;
+    let state = APP_STATE.get().ok_or("not initialized")?.clone();
+    let (puuid, region) = {
+        let t = state.inner.lock().await;
+        (t.puuid.clone(), t.region.clone())
+    };
+    let puuid = puuid.ok_or("no summoner")?;
+    let region = region.ok_or("no region")?;
+
+    let masteries: Vec<(i32, i64)> = match state.client.get_champion_masteries(&puuid, &region).await {
+        Ok(masteries) => masteries.iter()
+            .map(|m| (i16::from(m.champion_id) as i32, m.champion_points as i64))
+            .collect(),
+        Err(e) => {
+            warn!("Failed to fetch champion masteries for draft: {:?}", e);
+            Vec::new()
+        }
+    };
+    let store = state.client.store();
+    let pool = player_pool(&masteries, &store.matches_for(&puuid), &puuid);
+    let input = DraftInput { role, ally_picks, enemy_picks, bans };
+    let records = matchup_records(&store.stored_in_bucket(RankFilter::All), &pool, &input);
+    Ok(suggest_picks(&input, &pool, &records))
+}
+
+/// Create or replace a named rune page in the running League Client. Perks are checked
+/// against the patch's rune data before anything is sent.
+#[tauri::command]
+pub async fn push_rune_page(
+    name: String,
+    primary_style: i32,
+    sub_style: i32,
+    perk_ids: Vec<i32>,
+    shard_ids: Vec<i32>,
+) -> Result<serde_json::Value, String> {
+    push_page(&RunePage::new(&name, primary_style, sub_style, &perk_ids, &shard_ids)).await
+}
+
+/// Push the runes of one of `get_champion_builds`' builds, named after the champion and build
+#[tauri::command]
+pub async fn push_build_rune_page(
+    champion_id: i32,
+    role: String,
+    rank: Option<String>,
+    build_id: String,
+) -> Result<serde_json::Value, String> {
+    let champion = champion_by_id(champion_id).ok_or_else(|| format!("Unknown champion {}", champion_id))?;
+    let builds = collect_champion_builds(champion_id, &role, rank).await?;
+    let build = builds.iter()
+        .find(|b| b.id == build_id)
+        .ok_or_else(|| format!("No build {} for {}", build_id, champion.name))?;
+    push_page(&RunePage::from_build(&format!("{} - {}", champion.name, build.name), &build.runes)).await
+}
+
+async fn push_page(page: &RunePage) -> Result<serde_json::Value, String> {
+    let version = get_latest_ddragon_version().await.ok();
+    let styles = rune_data(version.as_deref()).await?;
+    validate_page(page, &styles)?;
+    LcuConnection::discover()?.replace_rune_page(page).await
+}
+
+/// Set the folder scanned for `.rofl` replays; empty falls back to the client's default
+#[tauri::command]
+pub async fn set_replay_folder(folder: String) -> Result<(), String> {
+    save_settings(&ReplaySettings { folder: Some(folder).filter(|f| !f.trim().is_empty()) })
+}
+
+/// Index new replays in the replay folder and return the library, newest first
+#[tauri::command]
+pub async fn scan_replays() -> Result<Vec<ReplayEntry>, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let folder = replay_folder().ok_or("No replay folder configured")?;
+    let index = tauri::async_runtime::spawn_blocking(move || scan_folder(&folder))
+        .await
+        .map_err(|e| format!("Replay scan failed: {}", e))??;
+    Ok(replay_library(&index, state.client.store()))
+}
+
+/// The replay library as last indexed, without rescanning
+#[tauri::command]
+pub async fn get_replay_library() -> Result<Vec<ReplayEntry>, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    Ok(replay_library(&load_index(), state.client.store()))
+}
+
+/// The tracked player's stats from replays of matches that aren't in the local store
+#[tauri::command]
+pub async fn get_imported_replay_games() -> Result<Vec<ImportedGame>, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let (puuid, name) = {
+        let t = state.inner.lock().await;
+        (t.puuid.clone(), t.name.clone())
+    };
+    let puuid = puuid.ok_or("No summoner tracked")?;
+    Ok(imported_games(&load_index(), state.client.store(), &puuid, name.as_deref()))
+}
+
+/// Timeline requests in flight at once when loading recent games
+const TIMELINE_CONCURRENCY: usize = 4;
+
+/// Most games a timeline report will load, whatever the caller asks for; each uncached
+/// timeline costs a rate-limited request
+const MAX_TIMELINE_GAMES: usize = 100;
+
+/// The player's `count` most recent stored games matching `filter` (at most `MAX_TIMELINE_GAMES`),
+/// each with its timeline. Games whose timeline can't be loaded are skipped.
+async fn recent_games_with_timelines(
+    state: &State,
+    puuid: &str,
+    region: &str,
+    filter: impl Fn(&riven::models::match_v5::Match) -> bool,
+    count: usize,
+) -> Vec<(riven::models::match_v5::Match, crate::timeline::MatchTimeline)> {
+    use futures::StreamExt;
+    use log::warn;
+    let mut recent = state.client.store().matches_for(puuid);
+    recent.retain(filter);
+    recent.sort_by_key(|m| std::cmp::Reverse(m.info.game_creation));
+    recent.truncate(count.min(MAX_TIMELINE_GAMES));
+
+    futures::stream::iter(recent)
+        .map(|m| async move {
+            match state.client.get_timeline_cached(&m.metadata.match_id, region).await {
+                Ok(Some(timeline)) => Some((m, timeline)),
+                Ok(None) => None,
+                Err(e) => {
+                    warn!("Failed to fetch timeline for {}: {:?}", m.metadata.match_id, e);
+                    None
+                }
+            }
+        })
+        .buffered(TIMELINE_CONCURRENCY)
+        .filter_map(|game| async move { game })
+        .collect()
+        .await
+}
+
+/// Recent games whose timelines feed the death and kill heatmaps
+const HEATMAP_GAMES: u32 = 20;
+
+/// Where the tracked player dies and gets kills on Summoner's Rift, binned by phase and role
+#[tauri::command]
+pub async fn get_position_heatmaps(role: Option<String>, count: Option<u32>) -> Result<PositionHeatmaps, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?.clone();
+    let (puuid, region) = {
+        let t = state.inner.lock().await;
+        (t.puuid.clone(), t.region.clone())
+    };
+    let puuid = puuid.ok_or("no summoner")?;
+    let region = region.ok_or("no region")?;
+
+    let games = recent_games_with_timelines(
+        &state,
+        &puuid,
+        &region,
+        |m| m.info.map_id == riven::consts::Map::SUMMONERS_RIFT,
+        count.unwrap_or(HEATMAP_GAMES) as usize,
+    )
+    .await;
+    let position = role.as_deref().and_then(role_to_position);
+    Ok(position_heatmaps(&games, &puuid, position))
+}
+
+const VISION_GAMES: u32 = 20;
+
+/// Ward placement, clearing and control ward uptime across recent Summoner's Rift games
+#[tauri::command]
+pub async fn get_vision_analysis(role: Option<String>, count: Option<u32>) -> Result<VisionReport, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?.clone();
+    let (puuid, region) = {
+        let t = state.inner.lock().await;
+        (t.puuid.clone(), t.region.clone())
+    };
+    let puuid = puuid.ok_or("no summoner")?;
+    let region = region.ok_or("no region")?;
+
+    let games = recent_games_with_timelines(
+        &state,
+        &puuid,
+        &region,
+        |m| m.info.map_id == riven::consts::Map::SUMMONERS_RIFT,
+        count.unwrap_or(VISION_GAMES) as usize,
+    )
+    .await;
+    let position = role.as_deref().and_then(role_to_position);
+    Ok(vision_report(&games, &puuid, position))
+}
+
+/// Item data for the current patch, or `None` (logged) so timelines can still be built without it
+async fn current_item_data() -> Option<ItemData> {
+    use log::warn;
+    let version = get_latest_ddragon_version().await.ok();
+    match item_data(version.as_deref()).await {
+        Ok(items) => Some(items),
+        Err(e) => {
+            warn!("Item data unavailable: {}", e);
+            None
+        }
+    }
+}
+
+/// Each player's build order in a stored match, with first- and two-item completion times
+#[tauri::command]
+pub async fn get_match_item_timeline(match_id: String) -> Result<Vec<PlayerItemTimeline>, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?.clone();
+    let region = state.inner.lock().await.region.clone().ok_or("no region")?;
+    let m = state.client.store().get(&match_id)
+        .ok_or_else(|| format!("Match {} is not stored", match_id))?;
+    let timeline = state.client.get_timeline_cached(&match_id, &region).await
+        .map_err(|e| format!("Failed to fetch timeline for {}: {:?}", match_id, e))?
+        .ok_or_else(|| format!("No timeline for {}", match_id))?;
+
+    let items = current_item_data().await;
+    Ok(match_item_timeline(&m, &timeline, items.as_ref()))
+}
+
+const ITEM_TIMING_GAMES: u32 = 30;
+
+/// First- and two-item completion times across the tracked player's recent games on a champion,
+/// against their win rate
+#[tauri::command]
+pub async fn get_item_timing(champion_id: i32, count: Option<u32>) -> Result<ItemTimingReport, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?.clone();
+    let (puuid, region) = {
+        let t = state.inner.lock().await;
+        (t.puuid.clone(), t.region.clone())
+    };
+    let puuid = puuid.ok_or("no summoner")?;
+    let region = region.ok_or("no region")?;
+
+    let games = recent_games_with_timelines(
+        &state,
+        &puuid,
+        &region,
+        |m| m.info.participants.iter().any(|p| p.puuid == puuid && champion_id_of(p) == champion_id),
+        count.unwrap_or(ITEM_TIMING_GAMES) as usize,
+    )
+    .await;
+
+    let items = current_item_data().await;
+    Ok(item_timing_report(&games, &puuid, champion_id, items.as_ref()))
+}
+
+/// Save a personal note about a player, shown whenever they appear in a lobby again.
+/// An empty note deletes it.
+#[tauri::command]
+pub async fn set_player_note(puuid: String, note: String) -> Result<Option<PlayerNote>, String> {
+    set_note(&puuid, &note)
+}
+
+#[tauri::command]
+pub async fn get_player_notes() -> Result<Vec<PlayerNote>, String> {
+    let mut notes: Vec<PlayerNote> = load_notes().into_values().collect();
+    notes.sort_by_key(|n| std::cmp::Reverse(n.updated_at));
+    Ok(notes)
+}
+
+async fn tracked_puuid() -> Result<String, String> {
+    let state = APP_STATE.get().ok_or("not initialized")?;
+    let puuid = state.inner.lock().await.puuid.clone();
+    puuid.ok_or_else(|| "No summoner tracked".to_string())
+}
+
+/// Create a goal such as "CS/min >= 7 in ranked mid", evaluated against games played from now on
+#[tauri::command]
+pub async fn create_goal(
+    metric: goals::GoalMetric,
+    comparator: goals::GoalComparator,
+    target: f32,
+    queue_ids: Option<Vec<i32>>,
+    role: Option<String>,
+    description: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let puuid = tracked_puuid().await?;
+    let now = chrono::Utc::now().timestamp_millis();
+    let goal = goals::Goal {
+        id: format!("goal_{}", now),
+        description: description.unwrap_or_else(|| format!("{:?} {:?} {}", metric, comparator, target)),
+        metric,
+        comparator,
+        target,
+        queue_ids: queue_ids.unwrap_or_default(),
+        role: role.filter(|r| !r.is_empty()),
+        active: true,
+        created_at: now,
+        paused_since: None,
+        paused_ranges: Vec::new(),
+        progress: goals::GoalProgress { last_evaluated: now, ..Default::default() },
+    };
+
+    goals::modify_goals(&puuid, |all| {
+        all.push(goal.clone());
+        Ok(())
+    })?;
+    serde_json::to_value(goal).map_err(|e| e.to_string())
+}
+
+/// All goals for the tracked player. Progress is updated by the post-game check and match sync.
+#[tauri::command]
+pub async fn get_goals() -> Result<serde_json::Value, String> {
+    let puuid = tracked_puuid().await?;
+    serde_json::to_value(goals::load_goals(&puuid)).map_err(|e| e.to_string())
+}
+
+/// Pause or resume a goal
+#[tauri::command]
+pub async fn set_goal_active(goal_id: String, active: bool) -> Result<(), String> {
+    let puuid = tracked_puuid().await?;
+    let now = chrono::Utc::now().timestamp_millis();
+    goals::modify_goals(&puuid, |all| {
+        let goal = all.iter_mut().find(|g| g.id == goal_id).ok_or("Goal not found")?;
+        // Games played while paused don't count
+        if active {
+            goal.resume(now);
+        } else {
+            goal.pause(now);
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn delete_goal(goal_id: String) -> Result<(), String> {
+    let puuid = tracked_puuid().await?;
+    goals::modify_goals(&puuid, |all| {
+        all.retain(|g| g.id != goal_id);
+        Ok(())
+    })
 }
 
 #[tauri::command]
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use riven::models::champion_mastery_v4::ChampionMastery;
+use riven::models::league_v4::LeagueEntry;
+use riven::models::match_v5::Match;
+
+use crate::match_analysis::{phase_averages, team_id_of};
+use crate::riot_client::{playstyle_traits, HistoricalMatchData, TraitGame};
+
+/// Champions listed in each player's mastery pool
+const CHAMPION_POOL_SIZE: usize = 5;
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerComparison {
+    pub riot_id: String,
+    pub puuid: String,
+    pub ranked: Vec<RankSummary>,
+    pub champion_pool: Vec<ChampionPoolEntry>,
+    pub sample_size: u32,
+    pub win_rate: f32,
+    pub kda: f32,
+    pub roles: Vec<RoleComparison>,
+    pub traits: Vec<String>,
+    pub phase_ratings: PhaseRatings,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RankSummary {
+    pub queue: String,
+    pub tier: String,
+    pub rank: String,
+    pub league_points: i32,
+    pub wins: i32,
+    pub losses: i32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionPoolEntry {
+    pub champion_id: i32,
+    pub champion_name: String,
+    pub level: i32,
+    pub points: i32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleComparison {
+    pub role: String,
+    pub games: u32,
+    pub win_rate: f32,
+    pub kda: f32,
+    pub cs_per_minute: f32,
+    pub gold_per_minute: f32,
+    pub damage_per_minute: f32,
+    pub vision_per_minute: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseRatings {
+    pub early_game_rating: f32,
+    pub mid_game_rating: f32,
+    pub late_game_rating: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedGames {
+    pub games: u32,
+    pub same_team_games: u32,
+    pub same_team_wins: u32,
+    pub opposing_games: u32,
+    /// Opposing games won by the first player
+    pub first_player_wins: u32,
+    pub match_ids: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadToHead {
+    pub first: PlayerComparison,
+    pub second: PlayerComparison,
+    pub shared: SharedGames,
+}
+
+/// API data fetched for one side of a comparison
+pub struct PlayerSources {
+    pub ranked: Vec<LeagueEntry>,
+    pub masteries: Vec<ChampionMastery>,
+    pub history: Vec<HistoricalMatchData>,
+}
+
+/// Keep only games in the queue (when given), then trim both samples to the same length
+pub fn equalize_samples(
+    first: Vec<HistoricalMatchData>,
+    second: Vec<HistoricalMatchData>,
+    queue_id: Option<i32>,
+) -> (Vec<HistoricalMatchData>, Vec<HistoricalMatchData>) {
+    let filter = |games: Vec<HistoricalMatchData>| -> Vec<HistoricalMatchData> {
+        let mut games: Vec<HistoricalMatchData> = games.into_iter()
+            .filter(|m| match queue_id {
+                Some(queue) => m.queue_id == queue,
+                None => true,
+            })
+            .collect();
+        games.sort_by_key(|m| std::cmp::Reverse(m.game_creation));
+        games
+    };
+    let (mut first, mut second) = (filter(first), filter(second));
+    let size = first.len().min(second.len());
+    first.truncate(size);
+    second.truncate(size);
+    (first, second)
+}
+
+fn role_comparisons(sample: &[HistoricalMatchData]) -> Vec<RoleComparison> {
+    let mut roles: HashMap<&str, Vec<&HistoricalMatchData>> = HashMap::new();
+    for m in sample {
+        let role = m.participant_data.team_position.as_str();
+        roles.entry(if role.is_empty() { "UNKNOWN" } else { role }).or_default().push(m);
+    }
+
+    let mut comparisons: Vec<RoleComparison> = roles.into_iter()
+        .map(|(role, games)| {
+            let minutes: f32 = games.iter().map(|m| (m.game_duration as f32 / 60.0).max(1.0)).sum();
+            let total = |stat: fn(&HistoricalMatchData) -> i32| games.iter().map(|m| stat(m) as f32).sum::<f32>();
+            let wins = games.iter().filter(|m| m.participant_data.win).count() as f32;
+            RoleComparison {
+                role: role.to_string(),
+                games: games.len() as u32,
+                win_rate: wins / games.len() as f32 * 100.0,
+                kda: (total(|m| m.participant_data.kills) + total(|m| m.participant_data.assists))
+                    / total(|m| m.participant_data.deaths).max(1.0),
+                cs_per_minute: total(|m| m.participant_data.total_minions_killed + m.participant_data.neutral_minions_killed) / minutes,
+                gold_per_minute: total(|m| m.participant_data.gold_earned) / minutes,
+                damage_per_minute: total(|m| m.participant_data.total_damage_dealt_to_champions) / minutes,
+                vision_per_minute: total(|m| m.participant_data.vision_score) / minutes,
+            }
+        })
+        .collect();
+    comparisons.sort_by_key(|r| std::cmp::Reverse(r.games));
+    comparisons
+}
+
+/// Side-by-side summary of one player over their filtered sample
+pub fn summarize_player(
+    riot_id: &str,
+    puuid: &str,
+    ranked: &[LeagueEntry],
+    masteries: &[ChampionMastery],
+    champion_names: &HashMap<u32, String>,
+    sample: &[HistoricalMatchData],
+) -> PlayerComparison {
+    let mut pool: Vec<&ChampionMastery> = masteries.iter().collect();
+    pool.sort_by_key(|m| std::cmp::Reverse(m.champion_points));
+
+    let games = sample.len() as f32;
+    let wins = sample.iter().filter(|m| m.participant_data.win).count() as f32;
+    let (kills, deaths, assists) = sample.iter().fold((0, 0, 0), |(k, d, a), m| {
+        (k + m.participant_data.kills, d + m.participant_data.deaths, a + m.participant_data.assists)
+    });
+    let scored: Vec<(i64, f32)> = sample.iter()
+        .filter_map(|m| m.performance_score.map(|score| (m.game_duration, score)))
+        .collect();
+    let [early, mid, late] = phase_averages(&scored);
+
+    PlayerComparison {
+        riot_id: riot_id.to_string(),
+        puuid: puuid.to_string(),
+        ranked: ranked.iter()
+            .map(|e| RankSummary {
+                queue: e.queue_type.to_string(),
+                tier: e.tier.as_ref().map(|t| t.to_string()).unwrap_or_default(),
+                rank: e.rank.as_ref().map(|r| r.to_string()).unwrap_or_default(),
+                league_points: e.league_points,
+                wins: e.wins,
+                losses: e.losses,
+            })
+            .collect(),
+        champion_pool: pool.into_iter()
+            .take(CHAMPION_POOL_SIZE)
+            .map(|m| {
+                let champion_id = i16::from(m.champion_id) as i32;
+                ChampionPoolEntry {
+                    champion_id,
+                    champion_name: champion_names.get(&(champion_id as u32)).cloned().unwrap_or_else(|| champion_id.to_string()),
+                    level: m.champion_level,
+                    points: m.champion_points,
+                }
+            })
+            .collect(),
+        sample_size: sample.len() as u32,
+        win_rate: if games > 0.0 { wins / games * 100.0 } else { 0.0 },
+        kda: (kills + assists) as f32 / deaths.max(1) as f32,
+        roles: role_comparisons(sample),
+        // Same equalized sample as every other stat, so the traits compare like for like
+        traits: playstyle_traits(&sample.iter().map(TraitGame::from_historical).collect::<Vec<_>>()),
+        phase_ratings: PhaseRatings {
+            early_game_rating: (early * 10.0).min(100.0),
+            mid_game_rating: (mid * 10.0).min(100.0),
+            late_game_rating: (late * 10.0).min(100.0),
+        },
+    }
+}
+
+/// Stored games both players appeared in, with their record together and against each other
+pub fn shared_games(stored: &[Match], first: &str, second: &str) -> SharedGames {
+    let mut shared = SharedGames::default();
+    for m in stored {
+        let a = m.info.participants.iter().find(|p| p.puuid == first);
+        let b = m.info.participants.iter().find(|p| p.puuid == second);
+        let (a, b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            _ => continue,
+        };
+
+        shared.games += 1;
+        shared.match_ids.push(m.metadata.match_id.clone());
+        if team_id_of(a) == team_id_of(b) {
+            shared.same_team_games += 1;
+            if a.win {
+                shared.same_team_wins += 1;
+            }
+        } else {
+            shared.opposing_games += 1;
+            if a.win {
+                shared.first_player_wins += 1;
+            }
+        }
+    }
+    shared
+}
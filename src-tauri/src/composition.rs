@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+
+use riven::models::match_v5::Match;
+
+use crate::champions::{champion_by_id, ChampionInfo};
+use crate::match_analysis::{champion_id_of, team_id_of};
+
+/// Magic damage dealers whose class tags don't include Mage
+const AP_CHAMPIONS: [&str; 13] = [
+    "Akali", "Ekko", "Fizz", "Gwen", "Kayle", "Mordekaiser", "Nunu",
+    "Singed", "Teemo", "Zac", "Sejuani", "Malphite", "Rammus",
+];
+
+/// Physical damage dealers tagged Mage
+const AD_CHAMPIONS: [&str; 2] = ["Ezreal", "Varus"];
+
+/// Champions whose damage is split between physical and magic
+const MIXED_CHAMPIONS: [&str; 8] = [
+    "KogMaw", "Kaisa", "Corki", "Shaco", "Jax", "Shyvana", "Volibear", "Udyr",
+];
+
+/// Champions that reliably start fights
+const ENGAGE_CHAMPIONS: [&str; 31] = [
+    "Alistar", "Amumu", "Leona", "Nautilus", "Rell", "Rakan", "Sejuani", "Zac",
+    "Ornn", "JarvanIV", "Malphite", "Thresh", "Vi", "MonkeyKing", "Sion", "Gragas",
+    "Kennen", "Diana", "Hecarim", "Rammus", "Blitzcrank", "Pyke", "Maokai", "Nunu",
+    "Skarner", "Poppy", "Galio", "Camille", "LeeSin", "Sett", "Lissandra",
+];
+
+/// Champions that whittle enemies down from range before fights
+const POKE_CHAMPIONS: [&str; 17] = [
+    "Xerath", "Ziggs", "Velkoz", "Jayce", "Lux", "Varus", "Ezreal", "Zoe", "Nidalee",
+    "Karma", "Corki", "Caitlyn", "Hwei", "Jhin", "Heimerdinger", "Zyra", "Senna",
+];
+
+/// Champions that get much stronger with levels and items
+const LATE_GAME_CHAMPIONS: [&str; 22] = [
+    "Kayle", "Kassadin", "Vayne", "KogMaw", "Jinx", "Veigar", "Nasus", "Azir",
+    "Smolder", "AurelionSol", "Cassiopeia", "Twitch", "Senna", "Jax", "Ryze", "Viktor",
+    "Vladimir", "Zeri", "Aphelios", "Gwen", "Kindred", "Sona",
+];
+
+/// Champions whose power peaks before the first item spikes fall off
+const EARLY_GAME_CHAMPIONS: [&str; 19] = [
+    "Renekton", "Draven", "LeeSin", "Elise", "Pantheon", "Lucian", "RekSai", "XinZhao",
+    "Kalista", "Nidalee", "Olaf", "Darius", "Shaco", "JarvanIV", "Kled", "Talon",
+    "Leblanc", "Qiyana", "Rengar",
+];
+
+/// Physical share at or above which a team counts as AD-heavy (and at or below 1 - this, AP-heavy)
+const DAMAGE_SKEW: f32 = 0.75;
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamComposition {
+    pub team_id: i32,
+    pub champions: Vec<String>,
+    /// Share of the team's expected damage that is physical, 0-1
+    pub physical_share: f32,
+    pub magic_share: f32,
+    pub frontline: u32,
+    pub engage: u32,
+    pub poke: u32,
+    /// Late-game champions minus early-game champions
+    pub scaling: i32,
+    pub crowd_control: f32,
+    pub categories: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompositionReport {
+    pub teams: Vec<TeamComposition>,
+    pub mismatches: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompositionWinRate {
+    pub category: String,
+    /// "ally" for the player's own team, "enemy" for the opposing one
+    pub side: String,
+    pub games: u32,
+    pub wins: u32,
+    pub win_rate: f32,
+    /// Win rate minus the player's overall win rate, in percentage points
+    pub win_rate_delta: f32,
+}
+
+fn listed(list: &[&str], champion: &ChampionInfo) -> bool {
+    list.contains(&champion.key)
+}
+
+fn physical_share(champion: &ChampionInfo) -> f32 {
+    if listed(&AD_CHAMPIONS, champion) {
+        1.0
+    } else if listed(&MIXED_CHAMPIONS, champion) {
+        0.5
+    } else if listed(&AP_CHAMPIONS, champion) || champion.has_tag("Mage") {
+        0.0
+    } else {
+        1.0
+    }
+}
+
+/// Tanks and supports deal less of a team's damage
+fn damage_weight(champion: &ChampionInfo) -> f32 {
+    match champion.primary_tag() {
+        "Tank" | "Support" => 0.5,
+        _ => 1.0,
+    }
+}
+
+fn crowd_control(champion: &ChampionInfo) -> f32 {
+    let base = if champion.has_tag("Tank") {
+        2.0
+    } else if champion.has_tag("Support") {
+        1.5
+    } else if champion.has_tag("Mage") {
+        1.0
+    } else {
+        0.5
+    };
+    if listed(&ENGAGE_CHAMPIONS, champion) { base + 1.0 } else { base }
+}
+
+/// Profile one team from its champion ids; unknown ids are skipped
+pub fn compose_team(team_id: i32, champion_ids: &[i32]) -> TeamComposition {
+    let champions: Vec<&ChampionInfo> = champion_ids.iter().filter_map(|id| champion_by_id(*id)).collect();
+
+    let total_weight: f32 = champions.iter().map(|c| damage_weight(c)).sum();
+    let physical = if total_weight > 0.0 {
+        champions.iter().map(|c| physical_share(c) * damage_weight(c)).sum::<f32>() / total_weight
+    } else {
+        0.5
+    };
+    let count = |list: &[&str]| champions.iter().filter(|c| listed(list, c)).count() as u32;
+
+    let mut composition = TeamComposition {
+        team_id,
+        champions: champions.iter().map(|c| c.name.to_string()).collect(),
+        physical_share: physical,
+        magic_share: 1.0 - physical,
+        frontline: champions.iter().filter(|c| c.has_tag("Tank") || c.primary_tag() == "Fighter").count() as u32,
+        engage: count(&ENGAGE_CHAMPIONS),
+        poke: count(&POKE_CHAMPIONS),
+        scaling: count(&LATE_GAME_CHAMPIONS) as i32 - count(&EARLY_GAME_CHAMPIONS) as i32,
+        crowd_control: champions.iter().map(|c| crowd_control(c)).sum(),
+        categories: Vec::new(),
+    };
+    composition.categories = categories(&composition);
+    composition
+}
+
+fn categories(team: &TeamComposition) -> Vec<String> {
+    let mut out = Vec::new();
+    if team.physical_share >= DAMAGE_SKEW {
+        out.push("adHeavy");
+    } else if team.physical_share <= 1.0 - DAMAGE_SKEW {
+        out.push("apHeavy");
+    } else {
+        out.push("mixedDamage");
+    }
+    if team.frontline == 0 {
+        out.push("noFrontline");
+    } else if team.frontline >= 3 {
+        out.push("heavyFrontline");
+    }
+    if team.engage >= 2 {
+        out.push("engage");
+    }
+    if team.poke >= 2 {
+        out.push("poke");
+    }
+    if team.scaling >= 2 {
+        out.push("lateGame");
+    } else if team.scaling <= -2 {
+        out.push("earlyGame");
+    }
+    out.into_iter().map(String::from).collect()
+}
+
+fn side_name(team_id: i32) -> &'static str {
+    if team_id == 100 { "Blue" } else { "Red" }
+}
+
+/// Notes on where one team's composition clearly differs from the other's
+fn mismatches(a: &TeamComposition, b: &TeamComposition) -> Vec<String> {
+    let mut notes = Vec::new();
+    for (team, other) in [(a, b), (b, a)] {
+        let name = side_name(team.team_id);
+        if team.physical_share >= DAMAGE_SKEW {
+            notes.push(format!("{} is AD-heavy ({:.0}% physical); armor is efficient against them", name, team.physical_share * 100.0));
+        } else if team.physical_share <= 1.0 - DAMAGE_SKEW {
+            notes.push(format!("{} is AP-heavy ({:.0}% magic); magic resist is efficient against them", name, team.magic_share * 100.0));
+        }
+        if team.frontline == 0 && other.frontline > 0 {
+            notes.push(format!("{} has no frontline", name));
+        }
+        if team.engage >= other.engage + 2 {
+            notes.push(format!("{} has much more engage ({} vs {})", name, team.engage, other.engage));
+        }
+        if team.poke >= 2 && other.engage == 0 {
+            notes.push(format!("{} can poke freely; the other team lacks engage", name));
+        }
+        if team.scaling >= other.scaling + 2 {
+            notes.push(format!("{} outscales; the other team should look to end early", name));
+        }
+    }
+    notes
+}
+
+/// Profile and compare two teams given as (team id, champion ids)
+pub fn compare_teams(teams: &[(i32, Vec<i32>)]) -> CompositionReport {
+    let teams: Vec<TeamComposition> = teams.iter().map(|(team_id, ids)| compose_team(*team_id, ids)).collect();
+    let mismatches = match teams.as_slice() {
+        [a, b] => mismatches(a, b),
+        _ => Vec::new(),
+    };
+    CompositionReport { teams, mismatches }
+}
+
+/// Group `(team_id, champion_id)` pairs into each team's champions, ordered by team id
+pub fn group_teams(picks: impl IntoIterator<Item = (i32, i32)>) -> Vec<(i32, Vec<i32>)> {
+    let mut teams: Vec<(i32, Vec<i32>)> = Vec::new();
+    for (team_id, champion_id) in picks {
+        match teams.iter_mut().find(|(id, _)| *id == team_id) {
+            Some((_, ids)) => ids.push(champion_id),
+            None => teams.push((team_id, vec![champion_id])),
+        }
+    }
+    teams.sort_by_key(|(id, _)| *id);
+    teams
+}
+
+fn match_teams(m: &Match) -> Vec<(i32, Vec<i32>)> {
+    group_teams(m.info.participants.iter().map(|p| (team_id_of(p), champion_id_of(p))))
+}
+
+pub fn match_composition(m: &Match) -> CompositionReport {
+    compare_teams(&match_teams(m))
+}
+
+/// The player's win rate by their own team's and the enemy team's composition categories
+pub fn composition_win_rates(matches: &[Match], puuid: &str) -> Vec<CompositionWinRate> {
+    let mut tallies: HashMap<(String, &str), (u32, u32)> = HashMap::new();
+    let (mut games, mut wins) = (0u32, 0u32);
+    for m in matches {
+        let me = match m.info.participants.iter().find(|p| p.puuid == puuid) {
+            Some(me) => me,
+            None => continue,
+        };
+        games += 1;
+        wins += me.win as u32;
+        for (team_id, ids) in match_teams(m) {
+            let side = if team_id == team_id_of(me) { "ally" } else { "enemy" };
+            for category in compose_team(team_id, &ids).categories {
+                let tally = tallies.entry((category, side)).or_default();
+                tally.0 += 1;
+                tally.1 += me.win as u32;
+            }
+        }
+    }
+
+    let overall = if games > 0 { wins as f32 / games as f32 * 100.0 } else { 0.0 };
+    let mut rates: Vec<CompositionWinRate> = tallies.into_iter()
+        .map(|((category, side), (games, wins))| {
+            let win_rate = wins as f32 / games as f32 * 100.0;
+            CompositionWinRate {
+                category,
+                side: side.to_string(),
+                games,
+                wins,
+                win_rate,
+                win_rate_delta: win_rate - overall,
+            }
+        })
+        .collect();
+    rates.sort_by_key(|r| std::cmp::Reverse(r.games));
+    rates
+}
@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use riven::models::match_v5::Match;
+
+use crate::builds::role_to_position;
+use crate::champions::champion_by_id;
+use crate::composition::compose_team;
+use crate::match_analysis::{champion_id_of, is_remake};
+use crate::match_store::StoredMatch;
+use crate::matchups::aggregate_matchups;
+
+/// Pseudo-games at 50% mixed into personal and matchup win rates
+const PRIOR_GAMES: f32 = 10.0;
+
+/// Mastery points at which the comfort bonus is maxed out
+const MASTERY_CAP: f32 = 300_000.0;
+
+/// Weight of the mastery bonus relative to personal win rate
+const MASTERY_WEIGHT: f32 = 0.1;
+
+/// Score added for each composition gap a pick fills
+const GAP_BONUS: f32 = 0.05;
+
+/// Suggestions returned
+const MAX_SUGGESTIONS: usize = 10;
+
+/// Champion-select state for the player's team, as read from the champ-select feed
+#[derive(Debug, Clone, Default)]
+pub struct DraftInput {
+    /// Assigned role ("mid", "support", ...)
+    pub role: String,
+    pub ally_picks: Vec<i32>,
+    pub enemy_picks: Vec<i32>,
+    pub bans: Vec<i32>,
+}
+
+/// One champion in the player's pool
+#[derive(Debug, Clone, Default)]
+pub struct PoolEntry {
+    pub champion_id: i32,
+    pub mastery_points: i64,
+    pub games: u32,
+    pub wins: u32,
+    /// Stored games on this champion in each position
+    pub positions: HashMap<String, u32>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftMatchup {
+    pub enemy_champion_id: i32,
+    pub games: u32,
+    pub wins: u32,
+    pub win_rate: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftSuggestion {
+    pub champion_id: i32,
+    pub champion_name: String,
+    pub score: f32,
+    pub comfort: f32,
+    pub matchup: f32,
+    pub composition: f32,
+    pub mastery_points: i64,
+    pub games: u32,
+    pub win_rate: Option<f32>,
+    pub matchups: Vec<DraftMatchup>,
+    pub reasons: Vec<String>,
+}
+
+fn shrunk_rate(wins: u32, games: u32) -> f32 {
+    (wins as f32 + PRIOR_GAMES * 0.5) / (games as f32 + PRIOR_GAMES)
+}
+
+/// The player's pool from champion mastery and their stored games
+pub fn player_pool(masteries: &[(i32, i64)], history: &[Match], puuid: &str) -> Vec<PoolEntry> {
+    let mut pool: HashMap<i32, PoolEntry> = masteries.iter()
+        .map(|&(champion_id, mastery_points)| (champion_id, PoolEntry { champion_id, mastery_points, ..Default::default() }))
+        .collect();
+    for p in history.iter()
+        .filter(|m| !is_remake(m))
+        .filter_map(|m| m.info.participants.iter().find(|p| p.puuid == puuid))
+    {
+        let champion_id = champion_id_of(p);
+        let entry = pool.entry(champion_id).or_insert_with(|| PoolEntry { champion_id, ..Default::default() });
+        entry.games += 1;
+        entry.wins += p.win as u32;
+        if !p.team_position.is_empty() {
+            *entry.positions.entry(p.team_position.clone()).or_default() += 1;
+        }
+    }
+    pool.into_values().collect()
+}
+
+/// Lane-paired records for each pool champion against the enemy picks, in the assigned role
+pub fn matchup_records(corpus: &[Arc<StoredMatch>], pool: &[PoolEntry], input: &DraftInput) -> HashMap<i32, Vec<DraftMatchup>> {
+    if input.enemy_picks.is_empty() {
+        return HashMap::new();
+    }
+    pool.iter()
+        .map(|entry| {
+            let records = aggregate_matchups(corpus, entry.champion_id, Some(&input.role))
+                .into_iter()
+                .filter(|m| input.enemy_picks.contains(&m.enemy_champion_id))
+                .map(|m| DraftMatchup {
+                    enemy_champion_id: m.enemy_champion_id,
+                    games: m.games,
+                    wins: m.wins,
+                    win_rate: m.win_rate,
+                })
+                .collect();
+            (entry.champion_id, records)
+        })
+        .collect()
+}
+
+/// The candidate's record against one enemy pick, even when they've never met in lane
+fn matchup(records: &HashMap<i32, Vec<DraftMatchup>>, champion_id: i32, enemy_id: i32) -> DraftMatchup {
+    records.get(&champion_id)
+        .and_then(|records| records.iter().find(|m| m.enemy_champion_id == enemy_id))
+        .cloned()
+        .unwrap_or(DraftMatchup { enemy_champion_id: enemy_id, games: 0, wins: 0, win_rate: 50.0 })
+}
+
+/// Composition gaps on the ally team that adding the candidate closes
+fn gaps_filled(ally_picks: &[i32], champion_id: i32) -> Vec<&'static str> {
+    if ally_picks.is_empty() {
+        return Vec::new();
+    }
+    let before = compose_team(0, ally_picks).categories;
+    let mut with_pick = ally_picks.to_vec();
+    with_pick.push(champion_id);
+    let after = compose_team(0, &with_pick).categories;
+    let closed = |category: &str| before.iter().any(|c| c == category) && !after.iter().any(|c| c == category);
+
+    let mut filled = Vec::new();
+    if closed("adHeavy") {
+        filled.push("Adds magic damage to an AD-heavy team");
+    }
+    if closed("apHeavy") {
+        filled.push("Adds physical damage to an AP-heavy team");
+    }
+    if closed("noFrontline") {
+        filled.push("Gives the team a frontline");
+    }
+    if !before.iter().any(|c| c == "engage") && after.iter().any(|c| c == "engage") {
+        filled.push("Gives the team reliable engage");
+    }
+    filled
+}
+
+/// Whether the champion can be played in the position, by static roles or the player's own games
+fn plays_position(entry: &PoolEntry, position: &str) -> bool {
+    if entry.positions.get(position).copied().unwrap_or(0) > 0 {
+        return true;
+    }
+    lane_champion(entry.champion_id, position)
+}
+
+/// Whether the champion is usually played in the position
+fn lane_champion(champion_id: i32, position: &str) -> bool {
+    champion_by_id(champion_id).is_some_and(|c| c.roles.iter().any(|r| role_to_position(r) == Some(position)))
+}
+
+/// Rank the player's pool for their assigned role against the enemy picks locked so far,
+/// using lane matchup records from `matchup_records`. Only likely lane opponents (enemy picks
+/// usually played in the role, or met there before) count towards the matchup score; the
+/// rest of the enemy team is left to the composition term.
+pub fn suggest_picks(input: &DraftInput, pool: &[PoolEntry], records: &HashMap<i32, Vec<DraftMatchup>>) -> Vec<DraftSuggestion> {
+    let position = role_to_position(&input.role);
+    let taken = |id: i32| input.ally_picks.contains(&id) || input.enemy_picks.contains(&id) || input.bans.contains(&id);
+
+    let mut suggestions: Vec<DraftSuggestion> = pool.iter()
+        .filter(|entry| !taken(entry.champion_id))
+        .filter(|entry| match position {
+            Some(position) => plays_position(entry, position),
+            None => true,
+        })
+        .filter_map(|entry| {
+            let champion = champion_by_id(entry.champion_id)?;
+            let mut reasons = Vec::new();
+
+            let personal = shrunk_rate(entry.wins, entry.games);
+            let mastery = (entry.mastery_points as f32 / MASTERY_CAP).min(1.0);
+            let comfort = (personal - 0.5) + mastery * MASTERY_WEIGHT;
+            if entry.games > 0 {
+                reasons.push(format!("{} wins in {} stored games", entry.wins, entry.games));
+            }
+            if entry.mastery_points > 0 {
+                reasons.push(format!("{} mastery points", entry.mastery_points));
+            }
+
+            let matchups: Vec<DraftMatchup> = input.enemy_picks.iter()
+                .map(|&enemy| matchup(records, entry.champion_id, enemy))
+                .filter(|m| m.games > 0 || match position {
+                    Some(position) => lane_champion(m.enemy_champion_id, position),
+                    None => true,
+                })
+                .collect();
+            let matchup_score = if matchups.is_empty() {
+                0.0
+            } else {
+                matchups.iter()
+                    .map(|m| shrunk_rate(m.wins, m.games) - 0.5)
+                    .sum::<f32>() / matchups.len() as f32
+            };
+            for m in matchups.iter().filter(|m| m.games > 0) {
+                let enemy = champion_by_id(m.enemy_champion_id).map(|c| c.name).unwrap_or("Unknown");
+                if m.win_rate >= 55.0 {
+                    reasons.push(format!("Wins {:.0}% of {} games against {}", m.win_rate, m.games, enemy));
+                } else if m.win_rate <= 45.0 {
+                    reasons.push(format!("Only {:.0}% in {} games against {}", m.win_rate, m.games, enemy));
+                }
+            }
+
+            let filled = gaps_filled(&input.ally_picks, entry.champion_id);
+            let composition = filled.len() as f32 * GAP_BONUS;
+            reasons.extend(filled.into_iter().map(String::from));
+
+            Some(DraftSuggestion {
+                champion_id: entry.champion_id,
+                champion_name: champion.name.to_string(),
+                score: comfort + matchup_score + composition,
+                comfort,
+                matchup: matchup_score,
+                composition,
+                mastery_points: entry.mastery_points,
+                games: entry.games,
+                win_rate: if entry.games > 0 { Some(entry.wins as f32 / entry.games as f32 * 100.0) } else { None },
+                matchups,
+                reasons,
+            })
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AHRI: i32 = 103;
+    const TALON: i32 = 91;
+    const SYNDRA: i32 = 134;
+    const ZED: i32 = 238;
+    const JINX: i32 = 222;
+    const LEE_SIN: i32 = 64;
+
+    fn entry(champion_id: i32) -> PoolEntry {
+        PoolEntry { champion_id, mastery_points: 50_000, games: 10, wins: 5, ..Default::default() }
+    }
+
+    fn record(enemy_champion_id: i32, games: u32, wins: u32) -> DraftMatchup {
+        DraftMatchup { enemy_champion_id, games, wins, win_rate: wins as f32 / games as f32 * 100.0 }
+    }
+
+    fn ranked(suggestions: &[DraftSuggestion]) -> Vec<i32> {
+        suggestions.iter().map(|s| s.champion_id).collect()
+    }
+
+    #[test]
+    fn skips_taken_and_banned_champions() {
+        let input = DraftInput {
+            role: "middle".to_string(),
+            ally_picks: vec![SYNDRA],
+            enemy_picks: vec![ZED],
+            bans: vec![TALON],
+        };
+        let pool = [entry(AHRI), entry(TALON), entry(SYNDRA), entry(ZED)];
+        let suggestions = suggest_picks(&input, &pool, &HashMap::new());
+        assert_eq!(ranked(&suggestions), vec![AHRI]);
+    }
+
+    #[test]
+    fn ranking_follows_matchup_records() {
+        let input = DraftInput { role: "middle".to_string(), enemy_picks: vec![ZED], ..Default::default() };
+        let pool = [entry(AHRI), entry(TALON)];
+
+        let records = HashMap::from([
+            (AHRI, vec![record(ZED, 20, 15)]),
+            (TALON, vec![record(ZED, 20, 5)]),
+        ]);
+        assert_eq!(ranked(&suggest_picks(&input, &pool, &records)), vec![AHRI, TALON]);
+
+        let records = HashMap::from([
+            (AHRI, vec![record(ZED, 20, 5)]),
+            (TALON, vec![record(ZED, 20, 15)]),
+        ]);
+        assert_eq!(ranked(&suggest_picks(&input, &pool, &records)), vec![TALON, AHRI]);
+    }
+
+    #[test]
+    fn only_lane_opponents_affect_matchup_score() {
+        let pool = [entry(AHRI)];
+        let records = HashMap::from([(AHRI, vec![record(ZED, 20, 15)])]);
+        let lane_only = DraftInput { role: "middle".to_string(), enemy_picks: vec![ZED], ..Default::default() };
+        let with_bot_lane = DraftInput { enemy_picks: vec![ZED, JINX], ..lane_only.clone() };
+
+        let lane_only = suggest_picks(&lane_only, &pool, &records);
+        let with_bot_lane = suggest_picks(&with_bot_lane, &pool, &records);
+        assert!(lane_only[0].matchup > 0.0);
+        assert_eq!(lane_only[0].matchup, with_bot_lane[0].matchup);
+        assert_eq!(with_bot_lane[0].matchups.len(), 1);
+    }
+
+    #[test]
+    fn magic_damage_fills_an_ad_heavy_team() {
+        let input = DraftInput { role: "middle".to_string(), ally_picks: vec![JINX, LEE_SIN], ..Default::default() };
+        let pool = [entry(TALON), entry(AHRI)];
+        let suggestions = suggest_picks(&input, &pool, &HashMap::new());
+
+        assert_eq!(ranked(&suggestions), vec![AHRI, TALON]);
+        assert!(suggestions[0].composition > 0.0);
+        assert_eq!(suggestions[1].composition, 0.0);
+    }
+}
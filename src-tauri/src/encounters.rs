@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use riven::models::match_v5::Match;
+
+use crate::match_analysis::{champion_id_of, team_id_of};
+use crate::storage::{data_path, read_json, write_json};
+
+const NOTES_FILE: &str = "player_notes.json";
+
+/// A personal note about another player, keyed by PUUID
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerNote {
+    pub puuid: String,
+    pub note: String,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, serde::Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EncounterRecord {
+    pub games: u32,
+    /// Games the tracked player won
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl EncounterRecord {
+    fn add(&mut self, won: bool) {
+        self.games += 1;
+        if won {
+            self.wins += 1;
+        } else {
+            self.losses += 1;
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PastChampion {
+    pub champion_id: i32,
+    pub champion_name: String,
+    pub games: u32,
+}
+
+/// What the tracked player's stored history says about one lobby member
+#[derive(Debug, serde::Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyEncounter {
+    pub puuid: String,
+    pub last_known_riot_id: Option<String>,
+    pub as_ally: EncounterRecord,
+    pub as_enemy: EncounterRecord,
+    pub champions_played: Vec<PastChampion>,
+    pub last_seen: Option<i64>,
+    pub last_match_id: Option<String>,
+    pub note: Option<PlayerNote>,
+}
+
+/// Serializes read-modify-write cycles on the notes file
+static NOTES_LOCK: Mutex<()> = Mutex::new(());
+
+fn notes_path() -> Option<std::path::PathBuf> {
+    data_path(NOTES_FILE)
+}
+
+pub fn load_notes() -> HashMap<String, PlayerNote> {
+    notes_path()
+        .and_then(|path| read_json::<HashMap<String, PlayerNote>>(&path))
+        .unwrap_or_default()
+}
+
+/// Save or replace the note for a PUUID; an empty note removes it
+pub fn set_note(puuid: &str, note: &str) -> Result<Option<PlayerNote>, String> {
+    let path = notes_path().ok_or("No data directory available for notes")?;
+    let _guard = NOTES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut notes = load_notes();
+    let saved = if note.trim().is_empty() {
+        notes.remove(puuid);
+        None
+    } else {
+        let entry = PlayerNote {
+            puuid: puuid.to_string(),
+            note: note.trim().to_string(),
+            updated_at: chrono::Utc::now().timestamp(),
+        };
+        notes.insert(puuid.to_string(), entry.clone());
+        Some(entry)
+    };
+    write_json(&path, &notes)?;
+    Ok(saved)
+}
+
+/// Look up each lobby member in the tracked player's stored matches.
+/// `matches` should be the tracked player's games, newest first.
+pub fn lobby_encounters(
+    matches: &[Match],
+    puuid: &str,
+    lobby: &[String],
+    notes: &HashMap<String, PlayerNote>,
+) -> Vec<LobbyEncounter> {
+    let mut encounters: HashMap<&str, LobbyEncounter> = lobby.iter()
+        .filter(|other| !other.is_empty() && other.as_str() != puuid)
+        .map(|other| (other.as_str(), LobbyEncounter {
+            puuid: other.clone(),
+            note: notes.get(other).cloned(),
+            ..Default::default()
+        }))
+        .collect();
+    let mut champions: HashMap<&str, Vec<PastChampion>> = HashMap::new();
+
+    for m in matches {
+        let me = match m.info.participants.iter().find(|p| p.puuid == puuid) {
+            Some(me) => me,
+            None => continue,
+        };
+        for other in &m.info.participants {
+            let encounter = match encounters.get_mut(other.puuid.as_str()) {
+                Some(encounter) => encounter,
+                None => continue,
+            };
+            if team_id_of(other) == team_id_of(me) {
+                encounter.as_ally.add(me.win);
+            } else {
+                encounter.as_enemy.add(me.win);
+            }
+            if encounter.last_seen.is_none() {
+                encounter.last_seen = Some(m.info.game_creation);
+                encounter.last_match_id = Some(m.metadata.match_id.clone());
+                encounter.last_known_riot_id = other.riot_id_name.clone().map(|name| match &other.riot_id_tagline {
+                    Some(tag) => format!("{}#{}", name, tag),
+                    None => name,
+                });
+            }
+
+            let played = champions.entry(other.puuid.as_str()).or_default();
+            let champion_id = champion_id_of(other);
+            match played.iter_mut().find(|c| c.champion_id == champion_id) {
+                Some(champion) => champion.games += 1,
+                None => played.push(PastChampion {
+                    champion_id,
+                    champion_name: other.champion_name.clone(),
+                    games: 1,
+                }),
+            }
+        }
+    }
+
+    lobby.iter()
+        .filter_map(|other| encounters.remove(other.as_str()))
+        .map(|mut encounter| {
+            let mut played = champions.remove(encounter.puuid.as_str()).unwrap_or_default();
+            played.sort_by_key(|c| std::cmp::Reverse(c.games));
+            encounter.champions_played = played;
+            encounter
+        })
+        .collect()
+}
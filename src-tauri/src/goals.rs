@@ -0,0 +1,266 @@
+use std::sync::Mutex;
+
+use log::warn;
+
+use crate::builds::role_to_position;
+use crate::match_analysis::is_remake_duration;
+use crate::riot_client::{HistoricalMatchData, ParticipantData};
+use crate::storage::{data_path, read_json, write_json};
+
+/// Per-game results kept on each goal for display
+const RECENT_RESULTS: usize = 20;
+
+/// Serializes read-modify-write cycles on the goals file (commands, post-game checks, sync)
+static GOALS_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum GoalMetric {
+    CsPerMinute,
+    Deaths,
+    Kills,
+    Assists,
+    Kda,
+    ControlWards,
+    WardsPlaced,
+    WardsKilled,
+    VisionScore,
+    VisionPerMinute,
+    GoldPerMinute,
+    DamagePerMinute,
+}
+
+impl GoalMetric {
+    pub fn value(&self, data: &ParticipantData, game_duration: i64) -> f32 {
+        let minutes = (game_duration as f32 / 60.0).max(1.0);
+        match self {
+            GoalMetric::CsPerMinute => (data.total_minions_killed + data.neutral_minions_killed) as f32 / minutes,
+            GoalMetric::Deaths => data.deaths as f32,
+            GoalMetric::Kills => data.kills as f32,
+            GoalMetric::Assists => data.assists as f32,
+            GoalMetric::Kda => (data.kills + data.assists) as f32 / data.deaths.max(1) as f32,
+            GoalMetric::ControlWards => data.detector_wards_placed as f32,
+            GoalMetric::WardsPlaced => data.wards_placed as f32,
+            GoalMetric::WardsKilled => data.wards_killed as f32,
+            GoalMetric::VisionScore => data.vision_score as f32,
+            GoalMetric::VisionPerMinute => data.vision_score as f32 / minutes,
+            GoalMetric::GoldPerMinute => data.gold_earned as f32 / minutes,
+            GoalMetric::DamagePerMinute => data.total_damage_dealt_to_champions as f32 / minutes,
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq)]
+pub enum GoalComparator {
+    #[serde(rename = "gte")]
+    AtLeast,
+    #[serde(rename = "lte")]
+    AtMost,
+}
+
+impl GoalComparator {
+    pub fn met(&self, value: f32, target: f32) -> bool {
+        match self {
+            GoalComparator::AtLeast => value >= target,
+            GoalComparator::AtMost => value <= target,
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GoalProgress {
+    pub games_evaluated: u32,
+    pub games_met: u32,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    /// `game_creation` of the newest evaluated match; only newer matches are reported as updates
+    pub last_evaluated: i64,
+    pub recent: Vec<GoalResult>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalResult {
+    pub match_id: String,
+    pub game_creation: i64,
+    pub value: f32,
+    pub met: bool,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Goal {
+    pub id: String,
+    pub description: String,
+    pub metric: GoalMetric,
+    pub comparator: GoalComparator,
+    pub target: f32,
+    /// Queue ids the goal applies to; empty means every queue
+    #[serde(default)]
+    pub queue_ids: Vec<i32>,
+    /// Role query ("mid", "support", ...); `None` means every role
+    #[serde(default)]
+    pub role: Option<String>,
+    pub active: bool,
+    pub created_at: i64,
+    /// When the current pause started, while the goal is inactive
+    #[serde(default)]
+    pub paused_since: Option<i64>,
+    /// Finished pauses as `[start, end]` (ms); games created in them don't count
+    #[serde(default)]
+    pub paused_ranges: Vec<[i64; 2]>,
+    #[serde(default)]
+    pub progress: GoalProgress,
+}
+
+/// Emitted as `goalProgress` for each goal a new game was counted against
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalUpdate {
+    pub goal_id: String,
+    pub description: String,
+    pub result: GoalResult,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub success_rate: f32,
+}
+
+impl Goal {
+    pub fn applies_to(&self, m: &HistoricalMatchData) -> bool {
+        if m.game_creation < self.created_at {
+            return false;
+        }
+        if self.paused_ranges.iter().any(|[from, to]| m.game_creation >= *from && m.game_creation < *to) {
+            return false;
+        }
+        if !self.queue_ids.is_empty() && !self.queue_ids.contains(&m.queue_id) {
+            return false;
+        }
+        match self.role.as_deref().and_then(role_to_position) {
+            Some(position) => m.participant_data.team_position == position,
+            None => true,
+        }
+    }
+
+    pub fn pause(&mut self, now: i64) {
+        if self.active {
+            self.active = false;
+            self.paused_since = Some(now);
+        }
+    }
+
+    pub fn resume(&mut self, now: i64) {
+        if !self.active {
+            self.active = true;
+            // Goals paused before pauses were recorded fall back to their last evaluated game
+            let from = self.paused_since.take().unwrap_or(self.progress.last_evaluated);
+            self.paused_ranges.push([from, now]);
+        }
+    }
+
+    fn record(&mut self, m: &HistoricalMatchData) -> GoalUpdate {
+        let value = self.metric.value(&m.participant_data, m.game_duration);
+        let met = self.comparator.met(value, self.target);
+        let progress = &mut self.progress;
+
+        progress.games_evaluated += 1;
+        if met {
+            progress.games_met += 1;
+            progress.current_streak += 1;
+            progress.best_streak = progress.best_streak.max(progress.current_streak);
+        } else {
+            progress.current_streak = 0;
+        }
+        progress.last_evaluated = m.game_creation;
+
+        let result = GoalResult {
+            match_id: m.match_id.clone(),
+            game_creation: m.game_creation,
+            value,
+            met,
+        };
+        progress.recent.insert(0, result.clone());
+        progress.recent.truncate(RECENT_RESULTS);
+
+        GoalUpdate {
+            goal_id: self.id.clone(),
+            description: self.description.clone(),
+            result,
+            current_streak: progress.current_streak,
+            best_streak: progress.best_streak,
+            success_rate: progress.games_met as f32 / progress.games_evaluated as f32 * 100.0,
+        }
+    }
+}
+
+fn goals_path(puuid: &str) -> Option<std::path::PathBuf> {
+    data_path(&format!("goals/{}.json", puuid))
+}
+
+pub fn load_goals(puuid: &str) -> Vec<Goal> {
+    goals_path(puuid)
+        .and_then(|path| read_json::<Vec<Goal>>(&path))
+        .unwrap_or_default()
+}
+
+pub fn save_goals(puuid: &str, goals: &[Goal]) -> Result<(), String> {
+    match goals_path(puuid) {
+        Some(path) => write_json(&path, &goals),
+        None => Err("No data directory available for goals".to_string()),
+    }
+}
+
+/// Rebuild each active goal's progress from every eligible match, oldest first, so matches that a
+/// later sync backfills are still counted in order. Remakes are skipped. Returns
+/// updates only for matches newer than the previous evaluation, i.e. the ones just played.
+pub fn evaluate_goals(goals: &mut [Goal], matches: &[HistoricalMatchData]) -> Vec<GoalUpdate> {
+    let mut ordered: Vec<&HistoricalMatchData> = matches.iter()
+        .filter(|m| !is_remake_duration(m.game_duration))
+        .collect();
+    ordered.sort_by_key(|m| m.game_creation);
+    // An empty store says nothing about progress; keep what was saved
+    if ordered.is_empty() {
+        return Vec::new();
+    }
+
+    let mut updates = Vec::new();
+    for goal in goals.iter_mut().filter(|g| g.active) {
+        let previous = goal.progress.last_evaluated;
+        goal.progress = GoalProgress { last_evaluated: previous, ..Default::default() };
+        for m in &ordered {
+            if goal.applies_to(m) {
+                let update = goal.record(m);
+                if m.game_creation > previous {
+                    updates.push(update);
+                }
+            }
+        }
+        goal.progress.last_evaluated = goal.progress.last_evaluated.max(previous);
+    }
+    updates
+}
+
+/// Load, change and save the player's goals while holding the goals lock.
+/// Nothing is saved when `f` fails.
+pub fn modify_goals<R>(puuid: &str, f: impl FnOnce(&mut Vec<Goal>) -> Result<R, String>) -> Result<R, String> {
+    let _guard = GOALS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut goals = load_goals(puuid);
+    let result = f(&mut goals)?;
+    save_goals(puuid, &goals)?;
+    Ok(result)
+}
+
+/// Evaluate the player's stored goals against `matches` and persist any progress
+pub fn update_goals(puuid: &str, matches: &[HistoricalMatchData]) -> Vec<GoalUpdate> {
+    let _guard = GOALS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut goals = load_goals(puuid);
+    let before: Vec<GoalProgress> = goals.iter().map(|g| g.progress.clone()).collect();
+    let updates = evaluate_goals(&mut goals, matches);
+    if goals.iter().zip(&before).any(|(goal, before)| goal.progress != *before) {
+        if let Err(e) = save_goals(puuid, &goals) {
+            warn!("{}", e);
+        }
+    }
+    updates
+}
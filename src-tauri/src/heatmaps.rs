@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use riven::models::match_v5::Match;
+
+use crate::timeline::{MatchTimeline, Position};
+
+/// Summoner's Rift coordinates run from 0 to roughly this on both axes
+pub const MAP_SIZE: i32 = 15_000;
+
+/// Cells per side of the binned grid
+pub const GRID_SIZE: usize = 25;
+
+/// Game phases by event time: name, start minute, end minute (exclusive)
+const PHASES: [(&str, i64, i64); 3] = [
+    ("early", 0, 14),
+    ("mid", 14, 25),
+    ("late", 25, i64::MAX),
+];
+
+/// Zones need this many events in a phase before they are called out
+const MIN_INSIGHT_EVENTS: u32 = 3;
+
+/// Share of a phase's events in one zone for it to be called out
+const MIN_INSIGHT_SHARE: f32 = 0.3;
+
+/// Distance from the river diagonal (x + y = MAP_SIZE) that still counts as river
+const RIVER_HALF_WIDTH: i32 = 1_500;
+
+/// Distance from the mid-lane diagonal (x = y) that still counts as mid lane
+const MID_HALF_WIDTH: i32 = 1_200;
+
+/// Width of the outer lanes along the map edges
+const SIDE_LANE_WIDTH: i32 = 2_000;
+
+/// Base corners: x + y below this is blue base, above MAP_SIZE * 2 - this is red base
+const BASE_EXTENT: i32 = 5_000;
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPoint {
+    pub match_id: String,
+    pub x: i32,
+    pub y: i32,
+    pub timestamp: i64,
+    pub phase: String,
+    pub role: String,
+    pub zone: String,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneCount {
+    pub zone: String,
+    pub count: u32,
+    pub share: f32,
+}
+
+/// Events for one phase or role, binned
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapBreakdown {
+    pub key: String,
+    pub count: u32,
+    /// `grid[row][column]`, row 0 at the bottom (y = 0)
+    pub grid: Vec<Vec<u32>>,
+    pub zones: Vec<ZoneCount>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapLayer {
+    pub total: u32,
+    pub grid: Vec<Vec<u32>>,
+    pub zones: Vec<ZoneCount>,
+    pub by_phase: Vec<HeatmapBreakdown>,
+    pub by_role: Vec<HeatmapBreakdown>,
+    pub points: Vec<EventPoint>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionHeatmaps {
+    pub games_analyzed: u32,
+    pub map_size: i32,
+    pub grid_size: usize,
+    pub deaths: HeatmapLayer,
+    pub kills: HeatmapLayer,
+    pub insights: Vec<String>,
+}
+
+/// Game phase ("early", "mid", "late") an event timestamp falls in
+pub fn phase_of(timestamp: i64) -> &'static str {
+    let minute = timestamp / 60_000;
+    PHASES.iter()
+        .find(|(_, start, end)| minute >= *start && minute < *end)
+        .map(|(name, _, _)| *name)
+        .unwrap_or("late")
+}
+
+fn phase_label(phase: &str) -> String {
+    match PHASES.iter().find(|(name, _, _)| *name == phase) {
+        Some((_, start, end)) if *end == i64::MAX => format!("after {} minutes", start),
+        Some((_, start, end)) => format!("at {}\u{2013}{} minutes", start, end),
+        None => phase.to_string(),
+    }
+}
+
+/// Named Summoner's Rift area for a map position
+pub fn zone_of(position: Position) -> &'static str {
+    let (x, y) = (position.x, position.y);
+    if x + y < BASE_EXTENT {
+        "blue base"
+    } else if x + y > MAP_SIZE * 2 - BASE_EXTENT {
+        "red base"
+    } else if x < SIDE_LANE_WIDTH || y > MAP_SIZE - SIDE_LANE_WIDTH {
+        "top lane"
+    } else if y < SIDE_LANE_WIDTH || x > MAP_SIZE - SIDE_LANE_WIDTH {
+        "bot lane"
+    } else if (x - y).abs() < MID_HALF_WIDTH {
+        "mid lane"
+    } else if (x + y - MAP_SIZE).abs() < RIVER_HALF_WIDTH {
+        "river"
+    } else if x + y < MAP_SIZE {
+        "blue jungle"
+    } else {
+        "red jungle"
+    }
+}
+
+fn empty_grid() -> Vec<Vec<u32>> {
+    vec![vec![0; GRID_SIZE]; GRID_SIZE]
+}
+
+fn cell(value: i32) -> usize {
+    ((value.clamp(0, MAP_SIZE - 1) as usize) * GRID_SIZE) / MAP_SIZE as usize
+}
+
+fn bin(points: &[&EventPoint]) -> (Vec<Vec<u32>>, Vec<ZoneCount>) {
+    let mut grid = empty_grid();
+    let mut zones: HashMap<&str, u32> = HashMap::new();
+    for p in points {
+        grid[cell(p.y)][cell(p.x)] += 1;
+        *zones.entry(p.zone.as_str()).or_default() += 1;
+    }
+    let mut zones: Vec<ZoneCount> = zones.into_iter()
+        .map(|(zone, count)| ZoneCount {
+            zone: zone.to_string(),
+            count,
+            share: count as f32 / points.len() as f32,
+        })
+        .collect();
+    zones.sort_by_key(|z| std::cmp::Reverse(z.count));
+    (grid, zones)
+}
+
+fn breakdown<F: Fn(&EventPoint) -> &str>(points: &[EventPoint], key_of: F) -> Vec<HeatmapBreakdown> {
+    let mut groups: Vec<(&str, Vec<&EventPoint>)> = Vec::new();
+    for p in points {
+        let key = key_of(p);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, members)) => members.push(p),
+            None => groups.push((key, vec![p])),
+        }
+    }
+    groups.into_iter()
+        .map(|(key, members)| {
+            let (grid, zones) = bin(&members);
+            HeatmapBreakdown { key: key.to_string(), count: members.len() as u32, grid, zones }
+        })
+        .collect()
+}
+
+fn layer(points: Vec<EventPoint>) -> HeatmapLayer {
+    let all: Vec<&EventPoint> = points.iter().collect();
+    let (grid, zones) = bin(&all);
+    let mut by_phase = breakdown(&points, |p| p.phase.as_str());
+    by_phase.sort_by_key(|b| PHASES.iter().position(|(name, _, _)| *name == b.key));
+    HeatmapLayer {
+        total: points.len() as u32,
+        grid,
+        zones,
+        by_phase,
+        by_role: breakdown(&points, |p| p.role.as_str()),
+        points,
+    }
+}
+
+/// "You die in the river at 14–25 minutes" style callouts
+fn insights(deaths: &HeatmapLayer, kills: &HeatmapLayer) -> Vec<String> {
+    let mut out = Vec::new();
+    for (layer, verb) in [(deaths, "die"), (kills, "get kills")] {
+        for phase in &layer.by_phase {
+            if let Some(top) = phase.zones.first() {
+                if top.count >= MIN_INSIGHT_EVENTS && top.share >= MIN_INSIGHT_SHARE {
+                    out.push(format!(
+                        "You {} in the {} {} ({} of {})",
+                        verb, top.zone, phase_label(&phase.key), top.count, phase.count
+                    ));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Where the player dies and gets kills, from `CHAMPION_KILL` events in each game's timeline
+pub fn position_heatmaps(games: &[(Match, MatchTimeline)], puuid: &str, role: Option<&str>) -> PositionHeatmaps {
+    let mut deaths = Vec::new();
+    let mut kills = Vec::new();
+    let mut games_analyzed = 0;
+
+    for (m, timeline) in games {
+        let me = match m.info.participants.iter().find(|p| p.puuid == puuid) {
+            Some(me) => me,
+            None => continue,
+        };
+        if role.is_some_and(|role| me.team_position != role) {
+            continue;
+        }
+        games_analyzed += 1;
+
+        let participant_id = me.participant_id;
+        for event in timeline.events_of_type("CHAMPION_KILL") {
+            let position = match event.position {
+                Some(position) => position,
+                None => continue,
+            };
+            let point = || EventPoint {
+                match_id: m.metadata.match_id.clone(),
+                x: position.x,
+                y: position.y,
+                timestamp: event.timestamp,
+                phase: phase_of(event.timestamp).to_string(),
+                role: if me.team_position.is_empty() { "UNKNOWN".to_string() } else { me.team_position.clone() },
+                zone: zone_of(position).to_string(),
+            };
+            if event.victim_id == Some(participant_id) {
+                deaths.push(point());
+            } else if event.killer_id == Some(participant_id) {
+                kills.push(point());
+            }
+        }
+    }
+
+    let deaths = layer(deaths);
+    let kills = layer(kills);
+    PositionHeatmaps {
+        games_analyzed,
+        map_size: MAP_SIZE,
+        grid_size: GRID_SIZE,
+        insights: insights(&deaths, &kills),
+        deaths,
+        kills,
+    }
+}
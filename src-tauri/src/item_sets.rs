@@ -0,0 +1,168 @@
+use std::path::{Path, PathBuf};
+
+use crate::builds::{role_to_position, ChampionBuild};
+use crate::storage::write_json;
+
+/// Summoner's Rift and Howling Abyss
+const ASSOCIATED_MAPS: [i32; 2] = [11, 12];
+
+/// Prefix for exported files so re-exports replace our own sets and leave the user's alone
+const FILE_PREFIX: &str = "league-view";
+
+/// Default League install locations, checked in order
+const INSTALL_CANDIDATES: [&str; 4] = [
+    "C:\\Riot Games\\League of Legends",
+    "D:\\Riot Games\\League of Legends",
+    "/Applications/League of Legends.app/Contents/LoL",
+    "~/Games/league-of-legends/drive_c/Riot Games/League of Legends",
+];
+
+/// League client item set (`Config/Champions/<Champ>/Recommended/*.json`)
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemSet {
+    pub title: String,
+    #[serde(rename = "type")]
+    pub set_type: String,
+    pub map: String,
+    pub mode: String,
+    pub priority: bool,
+    pub sortrank: i32,
+    pub associated_champions: Vec<i32>,
+    pub associated_maps: Vec<i32>,
+    pub blocks: Vec<ItemSetBlock>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+pub struct ItemSetBlock {
+    #[serde(rename = "type")]
+    pub block_type: String,
+    pub items: Vec<ItemSetItem>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+pub struct ItemSetItem {
+    /// Item ids are strings in the client format
+    pub id: String,
+    pub count: u32,
+}
+
+fn block(name: &str, items: &[i32]) -> Option<ItemSetBlock> {
+    if items.is_empty() {
+        return None;
+    }
+    let mut entries: Vec<ItemSetItem> = Vec::new();
+    for item in items {
+        let id = item.to_string();
+        match entries.iter_mut().find(|e| e.id == id) {
+            Some(entry) => entry.count += 1,
+            None => entries.push(ItemSetItem { id, count: 1 }),
+        }
+    }
+    Some(ItemSetBlock { block_type: name.to_string(), items: entries })
+}
+
+/// Convert an aggregated build into a client item set
+pub fn item_set_for(build: &ChampionBuild, champion_id: i32, sortrank: i32) -> ItemSet {
+    let role = build.role.as_deref().map(|r| format!(" {}", r)).unwrap_or_default();
+    let blocks = [
+        block("Starting Items", &build.starting_items),
+        block("Core", &build.items.core),
+        block("Boots", &build.items.boots),
+        block("Situational", &build.items.situational),
+    ];
+
+    ItemSet {
+        title: format!("{}{} ({:.0}% WR, {} games)", build.name, role, build.win_rate, build.games),
+        set_type: "custom".to_string(),
+        map: "any".to_string(),
+        mode: "any".to_string(),
+        priority: false,
+        sortrank,
+        associated_champions: vec![champion_id],
+        associated_maps: ASSOCIATED_MAPS.to_vec(),
+        blocks: blocks.into_iter().flatten().collect(),
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// The League install directory, from `LEAGUE_INSTALL_DIR` or the default locations
+pub fn detect_league_install() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("LEAGUE_INSTALL_DIR") {
+        let dir = PathBuf::from(dir);
+        if dir.is_dir() {
+            return Some(dir);
+        }
+    }
+    INSTALL_CANDIDATES.iter()
+        .copied()
+        .map(expand_home)
+        .find(|dir| dir.join("Config").is_dir())
+}
+
+fn sanitize(value: &str) -> String {
+    value.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect()
+}
+
+/// Prefix shared by every set we export for one role, so "mid" and "middle" land on the
+/// same files; "any" when no role was chosen
+fn role_prefix(role: &str) -> String {
+    let role = match role_to_position(role) {
+        Some(position) => position.to_lowercase(),
+        None if role.trim().is_empty() => "any".to_string(),
+        None => sanitize(&role.trim().to_lowercase()),
+    };
+    format!("{}-{}-", FILE_PREFIX, role)
+}
+
+fn file_stem(role: &str, build: &ChampionBuild) -> String {
+    format!("{}{}", role_prefix(role), sanitize(&build.id))
+}
+
+/// Delete sets from earlier exports for this role, so builds that dropped out don't linger
+fn remove_previous_sets(dir: &Path, role: &str) -> Result<(), String> {
+    let prefix = role_prefix(role);
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let ours = path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".json"));
+        if ours {
+            std::fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Write each build as an item set under `<base>/Config/Champions/<key>/Recommended`,
+/// replacing whatever an earlier export wrote for the same role
+pub fn write_item_sets(
+    base: &Path,
+    champion_key: &str,
+    champion_id: i32,
+    role: &str,
+    builds: &[ChampionBuild],
+) -> Result<Vec<PathBuf>, String> {
+    let dir = base.join("Config").join("Champions").join(champion_key).join("Recommended");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    remove_previous_sets(&dir, role)?;
+
+    let mut written = Vec::new();
+    for (index, build) in builds.iter().enumerate() {
+        let path = dir.join(format!("{}.json", file_stem(role, build)));
+        write_json(&path, &item_set_for(build, champion_id, index as i32))?;
+        written.push(path);
+    }
+    Ok(written)
+}
@@ -0,0 +1,418 @@
+use std::collections::HashMap;
+
+use log::warn;
+use reqwest::Client;
+use riven::models::match_v5::{Match, Participant};
+
+use crate::builds::{is_boots, is_completed_item};
+use crate::match_analysis::{champion_id_of, team_id_of};
+use crate::storage::{data_path, read_json, write_json};
+use crate::timeline::{ItemEvent, MatchTimeline};
+
+const ITEMS_CACHE: &str = "static/items.json";
+
+/// Item fields from Data Dragon's item.json that the build timeline needs
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct ItemInfo {
+    pub name: String,
+    pub gold: ItemGold,
+    /// Item ids this builds into; non-empty for components
+    #[serde(default)]
+    pub into: Vec<String>,
+    /// Item ids this is built from
+    #[serde(default)]
+    pub from: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct ItemGold {
+    pub total: i32,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ItemFile {
+    data: HashMap<String, ItemInfo>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct CachedItems {
+    version: String,
+    items: HashMap<String, ItemInfo>,
+}
+
+pub type ItemData = HashMap<i32, ItemInfo>;
+
+fn by_id(items: HashMap<String, ItemInfo>) -> ItemData {
+    items.into_iter()
+        .filter_map(|(id, info)| id.parse().ok().map(|id| (id, info)))
+        .collect()
+}
+
+/// Item static data for the patch, from the local cache when it's current
+pub async fn item_data(version: Option<&str>) -> Result<ItemData, String> {
+    let path = data_path(ITEMS_CACHE);
+    let cached = path.as_deref().and_then(read_json::<CachedItems>);
+
+    let version = match (version, &cached) {
+        (Some(version), _) => version.to_string(),
+        // Offline: any cached patch is better than nothing
+        (None, Some(cached)) => return Ok(by_id(cached.items.clone())),
+        (None, None) => return Err("Item data unavailable: no patch version and nothing cached".to_string()),
+    };
+    if let Some(cached) = cached.as_ref().filter(|c| c.version == version) {
+        return Ok(by_id(cached.items.clone()));
+    }
+
+    let url = format!("https://ddragon.leagueoflegends.com/cdn/{}/data/en_US/item.json", version);
+    let fetched: Result<ItemFile, reqwest::Error> = async {
+        Client::new().get(url).send().await?.error_for_status()?.json().await
+    }
+    .await;
+
+    match fetched {
+        Ok(file) => {
+            if let Some(path) = &path {
+                if let Err(e) = write_json(path, &CachedItems { version, items: file.data.clone() }) {
+                    warn!("Failed to cache item data: {}", e);
+                }
+            }
+            Ok(by_id(file.data))
+        }
+        Err(e) => match cached {
+            Some(cached) => {
+                warn!("Failed to fetch item data for {}, using cached {}: {}", version, cached.version, e);
+                Ok(by_id(cached.items))
+            }
+            None => Err(format!("Failed to fetch item data: {}", e)),
+        },
+    }
+}
+
+/// Trinket upgrades (Farsight Alteration, Oracle Lens) build from the starting trinket
+const TRINKET_UPGRADES: [i32; 2] = [3363, 3364];
+
+/// Support quest item upgrade chain
+const SUPPORT_QUEST_ITEMS: std::ops::RangeInclusive<i32> = 3865..=3877;
+
+/// Items that are only reached by transforming a finished item (Seraph's Embrace, Muramana,
+/// Fimbulwinter), never bought; building towards them doesn't make the base item a component
+const TRANSFORM_UPGRADES: [i32; 3] = [3040, 3042, 3121];
+
+/// Finished items: built from components and into nothing further (other than a transform), per
+/// the patch's item data when it's available
+pub fn is_completion(item: i32, items: Option<&ItemData>) -> bool {
+    if TRINKET_UPGRADES.contains(&item) || SUPPORT_QUEST_ITEMS.contains(&item) || is_boots(item) {
+        return false;
+    }
+    match items.and_then(|items| items.get(&item)) {
+        Some(info) => {
+            !info.from.is_empty()
+                && info.into.iter().all(|id| id.parse().is_ok_and(|id: i32| TRANSFORM_UPGRADES.contains(&id)))
+        }
+        None => is_completed_item(item),
+    }
+}
+
+/// Components that are not completed items in their own right
+fn is_component(item: i32, items: &ItemData) -> bool {
+    items.get(&item).is_some_and(|info| !info.into.is_empty()) && !is_completion(item, Some(items))
+}
+
+/// Value of owned items consumed when `item` is built, walking down the recipe for parts
+/// the player doesn't hold
+fn consume_parts(item: i32, inventory: &mut Vec<i32>, items: &ItemData) -> i32 {
+    let parts = match items.get(&item) {
+        Some(info) => &info.from,
+        None => return 0,
+    };
+    let mut consumed = 0;
+    for part in parts.iter().filter_map(|p| p.parse::<i32>().ok()) {
+        match inventory.iter().position(|&owned| owned == part) {
+            Some(index) => {
+                inventory.remove(index);
+                consumed += items.get(&part).map_or(0, |info| info.gold.total);
+            }
+            None => consumed += consume_parts(part, inventory, items),
+        }
+    }
+    consumed
+}
+
+/// Gold actually paid for each component purchase, replaying the build order against an inventory
+fn component_gold(order: &[ItemEvent], items: &ItemData) -> i32 {
+    let mut inventory: Vec<i32> = Vec::new();
+    let mut spent = 0;
+    for event in order {
+        if event.sold {
+            if let Some(index) = inventory.iter().position(|&owned| owned == event.item_id) {
+                inventory.remove(index);
+            }
+            continue;
+        }
+        let total = items.get(&event.item_id).map_or(0, |info| info.gold.total);
+        let paid = total - consume_parts(event.item_id, &mut inventory, items);
+        if is_component(event.item_id, items) {
+            spent += paid.max(0);
+        }
+        inventory.push(event.item_id);
+    }
+    spent
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletedItem {
+    pub item_id: i32,
+    pub timestamp: i64,
+}
+
+/// One player's build order in a game, with completion timings
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerItemTimeline {
+    pub participant_id: i32,
+    pub puuid: String,
+    pub champion_id: i32,
+    pub team_id: i32,
+    pub win: bool,
+    pub build_order: Vec<ItemEvent>,
+    /// First purchase of each completed item, in order
+    pub completed_items: Vec<CompletedItem>,
+    pub first_item_at: Option<i64>,
+    pub two_items_at: Option<i64>,
+    /// `None` when item data was unavailable
+    pub component_gold: Option<i32>,
+}
+
+pub fn player_item_timeline(participant: &Participant, timeline: &MatchTimeline, items: Option<&ItemData>) -> PlayerItemTimeline {
+    let build_order = timeline.build_order(participant.participant_id);
+    let mut completed_items: Vec<CompletedItem> = Vec::new();
+    for event in build_order.iter().filter(|e| !e.sold && is_completion(e.item_id, items)) {
+        if !completed_items.iter().any(|c| c.item_id == event.item_id) {
+            completed_items.push(CompletedItem { item_id: event.item_id, timestamp: event.timestamp });
+        }
+    }
+
+    PlayerItemTimeline {
+        participant_id: participant.participant_id,
+        puuid: participant.puuid.clone(),
+        champion_id: champion_id_of(participant),
+        team_id: team_id_of(participant),
+        win: participant.win,
+        first_item_at: completed_items.first().map(|c| c.timestamp),
+        two_items_at: completed_items.get(1).map(|c| c.timestamp),
+        component_gold: items.map(|items| component_gold(&build_order, items)),
+        completed_items,
+        build_order,
+    }
+}
+
+/// Build orders for all ten players in a game
+pub fn match_item_timeline(m: &Match, timeline: &MatchTimeline, items: Option<&ItemData>) -> Vec<PlayerItemTimeline> {
+    m.info.participants.iter()
+        .map(|p| player_item_timeline(p, timeline, items))
+        .collect()
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemTimingGame {
+    pub match_id: String,
+    pub game_creation: i64,
+    pub win: bool,
+    pub first_item: Option<i32>,
+    pub first_item_at: Option<i64>,
+    pub two_items_at: Option<i64>,
+    pub component_gold: Option<i32>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FirstItemTiming {
+    pub item_id: i32,
+    pub games: u32,
+    pub avg_completion_minutes: f32,
+    pub win_rate: f32,
+}
+
+/// Completion timings across the player's games on one champion
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemTimingReport {
+    pub champion_id: i32,
+    pub games_analyzed: u32,
+    pub win_rate: f32,
+    pub avg_first_item_minutes: Option<f32>,
+    pub avg_two_items_minutes: Option<f32>,
+    pub avg_component_gold: Option<f32>,
+    pub median_first_item_minutes: Option<f32>,
+    /// Win rate in games where the first item was finished at or before the median
+    pub fast_first_item_win_rate: Option<f32>,
+    pub slow_first_item_win_rate: Option<f32>,
+    pub by_first_item: Vec<FirstItemTiming>,
+    pub games: Vec<ItemTimingGame>,
+}
+
+fn minutes(ms: i64) -> f32 {
+    ms as f32 / 60_000.0
+}
+
+fn average(values: &[f32]) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f32>() / values.len() as f32)
+}
+
+fn win_rate<'a>(games: impl Iterator<Item = &'a ItemTimingGame>) -> Option<f32> {
+    let (mut count, mut wins) = (0u32, 0u32);
+    for game in games {
+        count += 1;
+        wins += game.win as u32;
+    }
+    if count == 0 {
+        return None;
+    }
+    Some(wins as f32 / count as f32 * 100.0)
+}
+
+/// Compare first- and two-item timings across the player's games on `champion_id`
+/// and how they relate to winning
+pub fn item_timing_report(
+    games: &[(Match, MatchTimeline)],
+    puuid: &str,
+    champion_id: i32,
+    items: Option<&ItemData>,
+) -> ItemTimingReport {
+    let mut timings: Vec<ItemTimingGame> = Vec::new();
+    for (m, timeline) in games {
+        let me = match m.info.participants.iter().find(|p| p.puuid == puuid) {
+            Some(me) if champion_id_of(me) == champion_id => me,
+            _ => continue,
+        };
+        let player = player_item_timeline(me, timeline, items);
+        timings.push(ItemTimingGame {
+            match_id: m.metadata.match_id.clone(),
+            game_creation: m.info.game_creation,
+            win: player.win,
+            first_item: player.completed_items.first().map(|c| c.item_id),
+            first_item_at: player.first_item_at,
+            two_items_at: player.two_items_at,
+            component_gold: player.component_gold,
+        });
+    }
+    timings.sort_by_key(|g| std::cmp::Reverse(g.game_creation));
+
+    let first_items: Vec<f32> = timings.iter().filter_map(|g| g.first_item_at).map(minutes).collect();
+    let two_items: Vec<f32> = timings.iter().filter_map(|g| g.two_items_at).map(minutes).collect();
+    let component_gold: Vec<f32> = timings.iter().filter_map(|g| g.component_gold).map(|g| g as f32).collect();
+
+    let mut sorted = first_items.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let median = sorted.get(sorted.len() / 2).copied();
+    let (fast_first_item_win_rate, slow_first_item_win_rate) = match median {
+        Some(median) => (
+            win_rate(timings.iter().filter(|g| g.first_item_at.is_some_and(|at| minutes(at) <= median))),
+            win_rate(timings.iter().filter(|g| g.first_item_at.is_some_and(|at| minutes(at) > median))),
+        ),
+        None => (None, None),
+    };
+
+    let mut by_first_item: Vec<FirstItemTiming> = Vec::new();
+    for item_id in timings.iter().filter_map(|g| g.first_item) {
+        if by_first_item.iter().any(|f| f.item_id == item_id) {
+            continue;
+        }
+        let same: Vec<&ItemTimingGame> = timings.iter().filter(|g| g.first_item == Some(item_id)).collect();
+        let times: Vec<f32> = same.iter().filter_map(|g| g.first_item_at).map(minutes).collect();
+        by_first_item.push(FirstItemTiming {
+            item_id,
+            games: same.len() as u32,
+            avg_completion_minutes: average(&times).unwrap_or(0.0),
+            win_rate: win_rate(same.into_iter()).unwrap_or(0.0),
+        });
+    }
+    by_first_item.sort_by_key(|f| std::cmp::Reverse(f.games));
+
+    ItemTimingReport {
+        champion_id,
+        games_analyzed: timings.len() as u32,
+        win_rate: win_rate(timings.iter()).unwrap_or(0.0),
+        avg_first_item_minutes: average(&first_items),
+        avg_two_items_minutes: average(&two_items),
+        avg_component_gold: average(&component_gold),
+        median_first_item_minutes: median,
+        fast_first_item_win_rate,
+        slow_first_item_win_rate,
+        by_first_item,
+        games: timings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(name: &str, total: i32, from: &[&str], into: &[&str]) -> ItemInfo {
+        ItemInfo {
+            name: name.to_string(),
+            gold: ItemGold { total },
+            from: from.iter().map(|id| id.to_string()).collect(),
+            into: into.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    fn items() -> ItemData {
+        HashMap::from([
+            (1036, info("Long Sword", 350, &[], &["3133"])),
+            (1028, info("Ruby Crystal", 400, &[], &["3071"])),
+            (3133, info("Caulfield's Warhammer", 1100, &["1036", "1036"], &["3071"])),
+            (3071, info("Black Cleaver", 3000, &["3133", "1028"], &[])),
+            (3070, info("Tear of the Goddess", 400, &[], &["3003"])),
+            (3003, info("Archangel's Staff", 2900, &["3070", "3802"], &["3040"])),
+            (3040, info("Seraph's Embrace", 2900, &["3003"], &[])),
+        ])
+    }
+
+    fn bought(item_id: i32, timestamp: i64) -> ItemEvent {
+        ItemEvent { item_id, timestamp, sold: false }
+    }
+
+    fn sold(item_id: i32, timestamp: i64) -> ItemEvent {
+        ItemEvent { item_id, timestamp, sold: true }
+    }
+
+    #[test]
+    fn recipe_consumes_owned_components() {
+        let order = [bought(1036, 0), bought(1036, 1), bought(3133, 2), bought(1028, 3), bought(3071, 4)];
+        // Two swords, the 400 combine cost of the Warhammer and the Ruby; the Cleaver is finished
+        assert_eq!(component_gold(&order, &items()), 350 + 350 + 400 + 400);
+    }
+
+    #[test]
+    fn sold_component_is_not_consumed() {
+        let order = [bought(1036, 0), sold(1036, 1), bought(3071, 2)];
+        let items = items();
+        assert_eq!(component_gold(&order, &items), 350);
+        assert!(is_completion(3071, Some(&items)));
+    }
+
+    #[test]
+    fn transforming_items_are_completed() {
+        let items = items();
+        assert!(is_completion(3003, Some(&items)));
+        assert!(!is_completion(3070, Some(&items)));
+        assert!(!is_component(3003, &items));
+        assert!(is_component(3070, &items));
+    }
+
+    #[test]
+    fn trinkets_and_quest_items_are_never_completed() {
+        let items = HashMap::from([
+            (3363, info("Farsight Alteration", 0, &["3340"], &[])),
+            (3869, info("Celestial Opposition", 400, &["3867"], &[])),
+        ]);
+        assert!(!is_completion(3363, Some(&items)));
+        assert!(!is_completion(3869, Some(&items)));
+    }
+}
@@ -0,0 +1,248 @@
+use std::path::Path;
+
+use log::info;
+use reqwest::{Client, Method};
+
+use crate::item_sets::detect_league_install;
+use crate::runes::RunePage;
+
+/// Set to point at a mock client server instead of the real League Client
+const BASE_URL_ENV: &str = "LCU_BASE_URL";
+const PASSWORD_ENV: &str = "LCU_PASSWORD";
+
+const PERK_PAGES: &str = "/lol-perks/v1/pages";
+const PERK_INVENTORY: &str = "/lol-perks/v1/inventory";
+
+/// Connection details for the League Client's local API
+#[derive(Debug, Clone)]
+pub struct LcuConnection {
+    pub base_url: String,
+    pub password: String,
+}
+
+#[derive(Debug, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ExistingPage {
+    id: i64,
+    name: String,
+    #[serde(default = "deletable_default")]
+    is_deletable: bool,
+    #[serde(default)]
+    current: bool,
+}
+
+#[derive(Debug, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct PerkInventory {
+    owned_page_count: usize,
+}
+
+fn deletable_default() -> bool {
+    true
+}
+
+impl LcuConnection {
+    pub fn new(base_url: &str, password: &str) -> Self {
+        LcuConnection {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    /// Parse a lockfile (`LeagueClient:<pid>:<port>:<password>:<protocol>`)
+    pub fn from_lockfile(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let fields: Vec<&str> = contents.trim().split(':').collect();
+        match fields.as_slice() {
+            [_, _, port, password, protocol] => Ok(LcuConnection::new(
+                &format!("{}://127.0.0.1:{}", protocol, port),
+                password,
+            )),
+            _ => Err(format!("Unrecognised lockfile format in {}", path.display())),
+        }
+    }
+
+    /// The environment override when set, otherwise the running client's lockfile
+    pub fn discover() -> Result<Self, String> {
+        if let Ok(base_url) = std::env::var(BASE_URL_ENV) {
+            return Ok(LcuConnection::new(&base_url, &std::env::var(PASSWORD_ENV).unwrap_or_default()));
+        }
+        let install = detect_league_install().ok_or("League install not found")?;
+        let lockfile = install.join("lockfile");
+        if !lockfile.is_file() {
+            return Err("League Client is not running (no lockfile)".to_string());
+        }
+        LcuConnection::from_lockfile(&lockfile)
+    }
+
+    fn client(&self) -> Result<Client, String> {
+        // The client serves a self-signed certificate on localhost
+        Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .map_err(|e| format!("Failed to build LCU client: {}", e))
+    }
+
+    async fn request(&self, method: Method, path: &str, body: Option<&RunePage>) -> Result<reqwest::Response, String> {
+        let mut request = self.client()?
+            .request(method.clone(), format!("{}{}", self.base_url, path))
+            .basic_auth("riot", Some(&self.password));
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = request.send().await
+            .map_err(|e| format!("LCU request {} {} failed: {}", method, path, e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("LCU {} {} returned {}: {}", method, path, status, text));
+        }
+        Ok(response)
+    }
+
+    async fn pages(&self) -> Result<Vec<ExistingPage>, String> {
+        self.request(Method::GET, PERK_PAGES, None).await?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse rune pages: {}", e))
+    }
+
+    async fn inventory(&self) -> Result<PerkInventory, String> {
+        self.request(Method::GET, PERK_INVENTORY, None).await?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse rune page inventory: {}", e))
+    }
+
+    async fn delete_page(&self, page: &ExistingPage) -> Result<(), String> {
+        info!("Replacing rune page {} ({})", page.name, page.id);
+        self.request(Method::DELETE, &format!("{}/{}", PERK_PAGES, page.id), None).await?;
+        Ok(())
+    }
+
+    /// Create `page`, first deleting any deletable page with the same name. When there is
+    /// none and every owned page is in use, the current page is replaced if it's deletable.
+    pub async fn replace_rune_page(&self, page: &RunePage) -> Result<serde_json::Value, String> {
+        let pages = self.pages().await?;
+        let same_name: Vec<&ExistingPage> = pages.iter().filter(|p| p.name == page.name && p.is_deletable).collect();
+        for existing in &same_name {
+            self.delete_page(existing).await?;
+        }
+        if same_name.is_empty() {
+            let used = pages.iter().filter(|p| p.is_deletable).count();
+            if used >= self.inventory().await?.owned_page_count {
+                let current = pages.iter()
+                    .find(|p| p.current && p.is_deletable)
+                    .ok_or("No free rune page: delete a page or select an editable one in the client")?;
+                self.delete_page(current).await?;
+            }
+        }
+        self.request(Method::POST, PERK_PAGES, Some(page)).await?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse created rune page: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    /// Minimal client API: serves canned pages and inventory and records every request
+    fn mock_client(pages: &'static str, owned_page_count: usize) -> (LcuConnection, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let request = request_line.split_whitespace().take(2).collect::<Vec<_>>().join(" ");
+                let response = match request.as_str() {
+                    "GET /lol-perks/v1/pages" => pages.to_string(),
+                    "GET /lol-perks/v1/inventory" => format!("{{\"ownedPageCount\":{}}}", owned_page_count),
+                    "POST /lol-perks/v1/pages" => String::from_utf8(body).unwrap(),
+                    _ => "{}".to_string(),
+                };
+                log.lock().unwrap().push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+        (LcuConnection::new(&base_url, "secret"), requests)
+    }
+
+    fn page(name: &str) -> RunePage {
+        RunePage::new(name, 8000, 8100, &[8010, 9111, 9104, 8299, 8139, 8135], &[5008, 5008, 5011])
+    }
+
+    #[tokio::test]
+    async fn replaces_page_with_the_same_name() {
+        let (lcu, requests) = mock_client(
+            r#"[{"id":1,"name":"Other","isDeletable":true,"current":true},{"id":2,"name":"League View","isDeletable":true}]"#,
+            2,
+        );
+        lcu.replace_rune_page(&page("League View")).await.unwrap();
+        assert_eq!(
+            *requests.lock().unwrap(),
+            ["GET /lol-perks/v1/pages", "DELETE /lol-perks/v1/pages/2", "POST /lol-perks/v1/pages"]
+        );
+    }
+
+    #[tokio::test]
+    async fn replaces_current_page_when_inventory_is_full() {
+        let (lcu, requests) = mock_client(
+            r#"[{"id":1,"name":"Other","isDeletable":true},{"id":2,"name":"Mine","isDeletable":true,"current":true},{"id":3,"name":"Preset","isDeletable":false}]"#,
+            2,
+        );
+        lcu.replace_rune_page(&page("League View")).await.unwrap();
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                "GET /lol-perks/v1/pages",
+                "GET /lol-perks/v1/inventory",
+                "DELETE /lol-perks/v1/pages/2",
+                "POST /lol-perks/v1/pages",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_when_no_page_can_be_freed() {
+        let (lcu, requests) = mock_client(
+            r#"[{"id":1,"name":"Other","isDeletable":true},{"id":3,"name":"Preset","isDeletable":false,"current":true}]"#,
+            1,
+        );
+        let error = lcu.replace_rune_page(&page("League View")).await.unwrap_err();
+        assert!(error.starts_with("No free rune page"), "{}", error);
+        assert!(!requests.lock().unwrap().iter().any(|r| r.starts_with("POST") || r.starts_with("DELETE")));
+    }
+}
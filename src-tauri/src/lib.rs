@@ -1,5 +1,34 @@
 pub mod commands;
 pub mod riot_client;
+pub mod match_analysis;
+pub mod match_store;
+pub mod storage;
+pub mod timeline;
+pub mod builds;
+pub mod champion_stats;
+pub mod champions;
+pub mod comparison;
+pub mod composition;
+pub mod draft;
+pub mod encounters;
+pub mod goals;
+pub mod heatmaps;
+pub mod item_sets;
+pub mod item_timeline;
+pub mod lcu;
+pub mod matchups;
+pub mod playtime;
+pub mod premades;
+pub mod recommendations;
+pub mod replays;
+pub mod runes;
+pub mod scouting;
+pub mod sessions;
+pub mod sync;
+pub mod synergy;
+pub mod trends;
+pub mod vision;
+pub mod win_probability;
 pub mod retry;
 use commands::{
     set_tracked_summoner, 
@@ -16,9 +45,38 @@ use commands::{
     get_champion_matchups,
     get_counter_data,
     get_detailed_champion_stats,
+    start_match_sync,
+    get_sync_status,
+    get_duo_synergy,
+    get_session_analysis,
+    get_playtime_heatmap,
+    get_performance_trends,
+    create_goal,
+    get_goals,
+    set_goal_active,
+    delete_goal,
+    get_recommendations,
+    compare_players,
+    set_player_note,
+    get_player_notes,
+    get_match_composition,
+    get_composition_win_rates,
+    get_draft_suggestions,
+    export_item_sets,
+    push_rune_page,
+    push_build_rune_page,
+    set_replay_folder,
+    scan_replays,
+    get_replay_library,
+    get_imported_replay_games,
+    get_position_heatmaps,
+    get_vision_analysis,
+    get_match_item_timeline,
+    get_item_timing,
     test_connection_with_retry
 };
 use std::sync::Arc;
+use tauri::Manager;
 use crate::riot_client::RiotClient;
 use crate::commands::{APP_STATE, State, Tracked, poll_loop};
 
@@ -63,6 +121,14 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .setup(move |app| {
             let app_handle = app.handle();
+            match app.path().app_data_dir() {
+                Ok(dir) => {
+                    let _ = storage::DATA_DIR.set(dir);
+                    let loaded = state.client.store().load();
+                    println!("Loaded {} stored matches", loaded);
+                }
+                Err(e) => eprintln!("Warning: No app data directory, match store is in-memory only: {}", e),
+            }
             tauri::async_runtime::spawn(poll_loop(app_handle.clone(), state));
             Ok(())
         })
@@ -81,6 +147,34 @@ pub fn run() {
             get_champion_matchups,
             get_counter_data,
             get_detailed_champion_stats,
+            start_match_sync,
+            get_sync_status,
+            get_duo_synergy,
+            get_session_analysis,
+            get_playtime_heatmap,
+            get_performance_trends,
+            create_goal,
+            get_goals,
+            set_goal_active,
+            delete_goal,
+            get_recommendations,
+            compare_players,
+            set_player_note,
+            get_player_notes,
+            get_match_composition,
+            get_composition_win_rates,
+            get_draft_suggestions,
+            export_item_sets,
+            push_rune_page,
+            push_build_rune_page,
+            set_replay_folder,
+            scan_replays,
+            get_replay_library,
+            get_imported_replay_games,
+            get_position_heatmaps,
+            get_vision_analysis,
+            get_match_item_timeline,
+            get_item_timing,
             test_connection_with_retry,
         ])
         .run(tauri::generate_context!())
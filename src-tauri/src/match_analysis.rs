@@ -0,0 +1,377 @@
+use riven::models::match_v5::{Match, Participant};
+
+use crate::timeline::MatchTimeline;
+
+/// Per-game context for one player relative to their own team and lane opponent
+#[derive(Debug, serde::Serialize, Clone)]
+pub struct TeamContext {
+    pub team_id: i32,
+    pub team_kills: i32,
+    pub kill_participation: f32,
+    pub damage_share: f32,
+    pub gold_share: f32,
+    pub tank_share: f32,
+    pub vision_share: f32,
+    pub tower_damage_share: f32,
+    pub jungle_control: f32,
+    pub objective_participation: ObjectiveParticipation,
+    pub lane_opponent: Option<LaneOpponentDiff>,
+}
+
+/// Player objective involvement relative to the team's objective counts.
+/// Epic monsters count kills and assists from the timeline's `ELITE_MONSTER_KILL` events;
+/// without a timeline dragons and barons fall back to last hits and heralds are unknown.
+/// Towers and inhibitors use takedowns.
+#[derive(Debug, serde::Serialize, Clone)]
+pub struct ObjectiveParticipation {
+    pub team_dragons: i32,
+    pub team_barons: i32,
+    pub team_heralds: i32,
+    pub team_towers: i32,
+    pub team_inhibitors: i32,
+    pub dragon_participation: f32,
+    pub baron_participation: f32,
+    pub herald_participation: Option<f32>,
+    pub tower_participation: f32,
+    pub inhibitor_participation: f32,
+    pub objective_damage_share: f32,
+}
+
+/// Stat differentials against the enemy in the same `team_position`
+#[derive(Debug, serde::Serialize, Clone)]
+pub struct LaneOpponentDiff {
+    pub opponent_puuid: String,
+    pub opponent_champion_id: i32,
+    pub opponent_champion_name: String,
+    pub team_position: String,
+    pub gold_diff: i32,
+    pub cs_diff: i32,
+    pub damage_diff: i32,
+    pub kills_diff: i32,
+    pub deaths_diff: i32,
+    pub vision_diff: i32,
+    pub level_diff: i32,
+}
+
+/// Numeric team id (100 blue, 200 red)
+pub fn team_id_of(participant: &Participant) -> i32 {
+    participant.team_id as i32
+}
+
+pub fn champion_id_of(participant: &Participant) -> i32 {
+    participant.champion().map(|c| c.0 as i32).unwrap_or(0)
+}
+
+pub fn creep_score(participant: &Participant) -> i32 {
+    participant.total_minions_killed + participant.neutral_minions_killed
+}
+
+fn share(part: f32, total: f32) -> f32 {
+    if total > 0.0 { part / total } else { 0.0 }
+}
+
+fn team_total<F>(team: &[&Participant], stat: F) -> f32
+where
+    F: Fn(&Participant) -> i32,
+{
+    team.iter().map(|&p| stat(p) as f32).sum()
+}
+
+/// Find the enemy playing the same `team_position` as the given participant
+pub fn find_lane_opponent<'a>(m: &'a Match, participant: &Participant) -> Option<&'a Participant> {
+    if participant.team_position.is_empty() {
+        return None;
+    }
+    m.info.participants.iter().find(|p| {
+        p.team_id != participant.team_id && p.team_position == participant.team_position
+    })
+}
+
+/// Epic monsters of `monster_type` the participant killed or assisted on, from the timeline
+fn elite_monster_takedowns(timeline: &MatchTimeline, participant_id: i32, monster_type: &str) -> i32 {
+    timeline.events_of_type("ELITE_MONSTER_KILL")
+        .filter(|e| e.monster_type.as_deref() == Some(monster_type))
+        .filter(|e| {
+            e.killer_id == Some(participant_id)
+                || e.assisting_participant_ids.as_ref().is_some_and(|ids| ids.contains(&participant_id))
+        })
+        .count() as i32
+}
+
+/// Build the team-relative context for `puuid` using all ten participants and both teams.
+/// The timeline, when there is one, supplies epic monster participation.
+pub fn compute_team_context(m: &Match, puuid: &str, timeline: Option<&MatchTimeline>) -> Option<TeamContext> {
+    let player = m.info.participants.iter().find(|p| p.puuid == puuid)?;
+    let allies: Vec<&Participant> = m.info.participants.iter()
+        .filter(|p| p.team_id == player.team_id)
+        .collect();
+    let enemies: Vec<&Participant> = m.info.participants.iter()
+        .filter(|p| p.team_id != player.team_id)
+        .collect();
+
+    let team_kills = team_total(&allies, |p| p.kills);
+    let team_damage = team_total(&allies, |p| p.total_damage_dealt_to_champions);
+    let team_gold = team_total(&allies, |p| p.gold_earned);
+    let team_tanked = team_total(&allies, |p| p.total_damage_taken + p.damage_self_mitigated);
+    let team_vision = team_total(&allies, |p| p.vision_score);
+    let team_turret_damage = team_total(&allies, |p| p.damage_dealt_to_turrets);
+    let team_objective_damage = team_total(&allies, |p| p.damage_dealt_to_objectives);
+    let ally_jungle = team_total(&allies, |p| p.neutral_minions_killed);
+    let enemy_jungle = team_total(&enemies, |p| p.neutral_minions_killed);
+
+    let team = m.info.teams.iter().find(|t| t.team_id == player.team_id);
+    let (team_dragons, team_barons, team_heralds, team_towers, team_inhibitors) = team
+        .map(|t| (
+            t.objectives.dragon.kills,
+            t.objectives.baron.kills,
+            t.objectives.rift_herald.kills,
+            t.objectives.tower.kills,
+            t.objectives.inhibitor.kills,
+        ))
+        .unwrap_or((0, 0, 0, 0, 0));
+
+    let takedowns = |monster_type: &str| timeline.map(|t| elite_monster_takedowns(t, player.participant_id, monster_type));
+    let (dragon_takedowns, baron_takedowns) = match (takedowns("DRAGON"), takedowns("BARON_NASHOR")) {
+        (Some(dragons), Some(barons)) => (dragons, barons),
+        _ => (player.dragon_kills, player.baron_kills),
+    };
+
+    let objective_participation = ObjectiveParticipation {
+        team_dragons,
+        team_barons,
+        team_heralds,
+        team_towers,
+        team_inhibitors,
+        dragon_participation: share(dragon_takedowns as f32, team_dragons as f32).min(1.0),
+        baron_participation: share(baron_takedowns as f32, team_barons as f32).min(1.0),
+        herald_participation: takedowns("RIFTHERALD").map(|heralds| share(heralds as f32, team_heralds as f32).min(1.0)),
+        tower_participation: share(player.turret_takedowns.unwrap_or(0) as f32, team_towers as f32).min(1.0),
+        inhibitor_participation: share(player.inhibitor_takedowns.unwrap_or(0) as f32, team_inhibitors as f32).min(1.0),
+        objective_damage_share: share(player.damage_dealt_to_objectives as f32, team_objective_damage),
+    };
+
+    let lane_opponent = find_lane_opponent(m, player).map(|opp| LaneOpponentDiff {
+        opponent_puuid: opp.puuid.clone(),
+        opponent_champion_id: champion_id_of(opp),
+        opponent_champion_name: opp.champion_name.clone(),
+        team_position: player.team_position.clone(),
+        gold_diff: player.gold_earned - opp.gold_earned,
+        cs_diff: creep_score(player) - creep_score(opp),
+        damage_diff: player.total_damage_dealt_to_champions - opp.total_damage_dealt_to_champions,
+        kills_diff: player.kills - opp.kills,
+        deaths_diff: player.deaths - opp.deaths,
+        vision_diff: player.vision_score - opp.vision_score,
+        level_diff: player.champ_level - opp.champ_level,
+    });
+
+    Some(TeamContext {
+        team_id: team_id_of(player),
+        team_kills: team_kills as i32,
+        kill_participation: share((player.kills + player.assists) as f32, team_kills).min(1.0),
+        damage_share: share(player.total_damage_dealt_to_champions as f32, team_damage),
+        gold_share: share(player.gold_earned as f32, team_gold),
+        tank_share: share((player.total_damage_taken + player.damage_self_mitigated) as f32, team_tanked),
+        vision_share: share(player.vision_score as f32, team_vision),
+        tower_damage_share: share(player.damage_dealt_to_turrets as f32, team_turret_damage),
+        jungle_control: share(ally_jungle, ally_jungle + enemy_jungle),
+        objective_participation,
+        lane_opponent,
+    })
+}
+
+/// Composite 0–10 rating for one participant in one game
+#[derive(Debug, serde::Serialize, Clone)]
+pub struct PerformanceScore {
+    pub puuid: String,
+    pub riot_id_name: String,
+    pub champion_id: i32,
+    pub champion_name: String,
+    pub team_id: i32,
+    pub team_position: String,
+    pub win: bool,
+    pub score: f32,
+    pub rank: u32,
+    pub is_mvp: bool,
+    pub is_ace: bool,
+    pub components: ScoreComponents,
+}
+
+/// Individual 0–10 sub-scores, where 5.0 means "on par for the role"
+#[derive(Debug, serde::Serialize, Clone)]
+pub struct ScoreComponents {
+    pub kda: f32,
+    pub damage: f32,
+    pub gold: f32,
+    pub vision: f32,
+    pub cs: f32,
+    pub objectives: f32,
+}
+
+/// Games ending before this were decided in the early game
+pub const EARLY_DECIDED_SECS: i64 = 25 * 60;
+/// Games ending before this (and after the early cutoff) were decided in the mid game
+pub const MID_DECIDED_SECS: i64 = 35 * 60;
+
+/// Average composite score of `(game_duration, score)` pairs, bucketed by the phase
+/// the game was decided in. Phases with no games fall back to the overall average.
+pub fn phase_averages(games: &[(i64, f32)]) -> [f32; 3] {
+    let mut phases: [Vec<f32>; 3] = [Vec::new(), Vec::new(), Vec::new()];
+    for &(duration, score) in games {
+        let phase = if duration < EARLY_DECIDED_SECS {
+            0
+        } else if duration < MID_DECIDED_SECS {
+            1
+        } else {
+            2
+        };
+        phases[phase].push(score);
+    }
+
+    let overall = if games.is_empty() {
+        5.0
+    } else {
+        games.iter().map(|(_, score)| score).sum::<f32>() / games.len() as f32
+    };
+    phases.map(|scores| {
+        if scores.is_empty() { overall } else { scores.iter().sum::<f32>() / scores.len() as f32 }
+    })
+}
+
+/// Role expectations used to normalise raw stats
+pub struct RoleBenchmark {
+    pub damage_share: f32,
+    pub gold_share: f32,
+    pub vision_per_min: f32,
+    pub cs_per_min: f32,
+    // kda, damage, gold, vision, cs, objectives
+    weights: [f32; 6],
+}
+
+pub fn role_benchmark(team_position: &str) -> RoleBenchmark {
+    match team_position {
+        "TOP" => RoleBenchmark {
+            damage_share: 0.22, gold_share: 0.21, vision_per_min: 0.6, cs_per_min: 7.0,
+            weights: [0.25, 0.20, 0.15, 0.10, 0.15, 0.15],
+        },
+        "JUNGLE" => RoleBenchmark {
+            damage_share: 0.18, gold_share: 0.19, vision_per_min: 1.0, cs_per_min: 5.5,
+            weights: [0.25, 0.15, 0.10, 0.15, 0.10, 0.25],
+        },
+        "MIDDLE" => RoleBenchmark {
+            damage_share: 0.26, gold_share: 0.22, vision_per_min: 0.7, cs_per_min: 7.5,
+            weights: [0.25, 0.25, 0.15, 0.10, 0.15, 0.10],
+        },
+        "BOTTOM" => RoleBenchmark {
+            damage_share: 0.27, gold_share: 0.23, vision_per_min: 0.6, cs_per_min: 8.0,
+            weights: [0.25, 0.25, 0.15, 0.05, 0.20, 0.10],
+        },
+        "UTILITY" => RoleBenchmark {
+            damage_share: 0.09, gold_share: 0.14, vision_per_min: 2.0, cs_per_min: 1.2,
+            weights: [0.35, 0.10, 0.05, 0.35, 0.00, 0.15],
+        },
+        _ => RoleBenchmark {
+            damage_share: 0.20, gold_share: 0.20, vision_per_min: 1.0, cs_per_min: 6.0,
+            weights: [0.25, 0.20, 0.15, 0.15, 0.10, 0.15],
+        },
+    }
+}
+
+/// Map a stat relative to its benchmark onto 0–10, with the benchmark itself at 5
+fn relative_score(value: f32, benchmark: f32) -> f32 {
+    if benchmark <= 0.0 {
+        return 5.0;
+    }
+    (5.0 * value / benchmark).clamp(0.0, 10.0)
+}
+
+/// Games shorter than this are remakes and are not scored
+const MIN_SCORED_DURATION_SECS: i64 = 300;
+
+/// Whether a game of this length (in seconds) was a remake
+pub fn is_remake_duration(game_duration: i64) -> bool {
+    game_duration < MIN_SCORED_DURATION_SECS
+}
+
+/// Whether the game ended as a remake
+pub fn is_remake(m: &Match) -> bool {
+    is_remake_duration(m.info.game_duration)
+}
+
+/// Score and rank all ten participants. MVP goes to the best player on the
+/// winning team and ACE to the best player on the losing team.
+pub fn score_match(m: &Match) -> Vec<PerformanceScore> {
+    if is_remake(m) {
+        return Vec::new();
+    }
+    let minutes = m.info.game_duration as f32 / 60.0;
+
+    let mut scores: Vec<PerformanceScore> = m.info.participants.iter()
+        .filter_map(|p| {
+            let context = compute_team_context(m, &p.puuid, None)?;
+            let benchmark = role_benchmark(&p.team_position);
+
+            let kda_ratio = (p.kills + p.assists) as f32 / p.deaths.max(1) as f32;
+            // Saturating curve: a 3.0 KDA lands at 5, a perfect game approaches 10
+            let kda_curve = 10.0 * kda_ratio / (kda_ratio + 3.0);
+            let kda = (0.5 * kda_curve + 0.5 * context.kill_participation * 10.0).clamp(0.0, 10.0);
+
+            let objectives = ((relative_score(context.objective_participation.objective_damage_share, 0.2)
+                + context.objective_participation.tower_participation * 10.0) / 2.0).clamp(0.0, 10.0);
+
+            let components = ScoreComponents {
+                kda,
+                damage: relative_score(context.damage_share, benchmark.damage_share),
+                gold: relative_score(context.gold_share, benchmark.gold_share),
+                vision: relative_score(p.vision_score as f32 / minutes, benchmark.vision_per_min),
+                cs: relative_score(creep_score(p) as f32 / minutes, benchmark.cs_per_min),
+                objectives,
+            };
+
+            let values = [
+                components.kda,
+                components.damage,
+                components.gold,
+                components.vision,
+                components.cs,
+                components.objectives,
+            ];
+            let score: f32 = values.iter()
+                .zip(benchmark.weights.iter())
+                .map(|(v, w)| v * w)
+                .sum();
+
+            Some(PerformanceScore {
+                puuid: p.puuid.clone(),
+                riot_id_name: p.riot_id_name.clone().unwrap_or_else(|| p.summoner_name.clone()),
+                champion_id: champion_id_of(p),
+                champion_name: p.champion_name.clone(),
+                team_id: team_id_of(p),
+                team_position: p.team_position.clone(),
+                win: p.win,
+                score: (score * 10.0).round() / 10.0,
+                rank: 0,
+                is_mvp: false,
+                is_ace: false,
+                components,
+            })
+        })
+        .collect();
+
+    scores.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    for (index, entry) in scores.iter_mut().enumerate() {
+        entry.rank = index as u32 + 1;
+    }
+    if let Some(mvp) = scores.iter_mut().find(|s| s.win) {
+        mvp.is_mvp = true;
+    }
+    if let Some(ace) = scores.iter_mut().find(|s| !s.win) {
+        ace.is_ace = true;
+    }
+
+    scores
+}
+
+/// Composite score for a single player, if they were in the game
+pub fn player_score(m: &Match, puuid: &str) -> Option<f32> {
+    score_match(m).into_iter().find(|s| s.puuid == puuid).map(|s| s.score)
+}
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+use log::warn;
+use riven::models::match_v5::Match;
+
+use crate::storage::{data_path, data_subdir, read_json, write_json};
+use crate::timeline::MatchTimeline;
+
+const MATCHES_DIR: &str = "matches";
+
+/// Ranked tiers from lowest to highest
+pub const TIER_ORDER: [&str; 10] = [
+    "IRON", "BRONZE", "SILVER", "GOLD", "PLATINUM",
+    "EMERALD", "DIAMOND", "MASTER", "GRANDMASTER", "CHALLENGER",
+];
+
+pub fn tier_index(tier: &str) -> Option<usize> {
+    let tier = tier.to_uppercase();
+    TIER_ORDER.iter().position(|t| *t == tier)
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct StoredMatch {
+    #[serde(rename = "match")]
+    pub data: Match,
+    #[serde(default)]
+    pub timeline: Option<MatchTimeline>,
+    /// Solo-queue tier of the account that ingested the match, at ingest time.
+    /// Used as an approximate rank bucket for the whole lobby.
+    #[serde(default)]
+    pub rank_tier: Option<String>,
+    pub stored_at: i64,
+}
+
+/// Rank bucket filter such as "all", "gold" or "diamond+"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RankFilter {
+    All,
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl RankFilter {
+    pub fn parse(rank: Option<&str>) -> Self {
+        let raw = rank.unwrap_or("all").trim().to_uppercase();
+        let (name, at_least) = match raw.strip_suffix('+') {
+            Some(name) => (name.to_string(), true),
+            None => (raw.clone(), false),
+        };
+        match tier_index(&name) {
+            Some(index) if at_least => RankFilter::AtLeast(index),
+            Some(index) => RankFilter::Exactly(index),
+            None => RankFilter::All,
+        }
+    }
+
+    /// Untagged matches only pass the `All` filter
+    pub fn accepts(&self, tier: Option<&str>) -> bool {
+        let index = tier.and_then(tier_index);
+        match (self, index) {
+            (RankFilter::All, _) => true,
+            (RankFilter::Exactly(wanted), Some(index)) => index == *wanted,
+            (RankFilter::AtLeast(min), Some(index)) => index >= *min,
+            (_, None) => false,
+        }
+    }
+}
+
+/// Local corpus of full match-v5 payloads, one JSON file per match. Entries are shared, so
+/// bucket queries don't copy timelines; changes replace the entry.
+#[derive(Debug, Default)]
+pub struct MatchStore {
+    matches: RwLock<HashMap<String, Arc<StoredMatch>>>,
+    /// Held across a change and its write to disk, so writes land in the order the changes were
+    /// made while readers only wait for the in-memory update
+    persisting: Mutex<()>,
+}
+
+impl MatchStore {
+    /// Load every stored match from disk, returning how many were read
+    pub fn load(&self) -> usize {
+        let dir = match data_subdir(MATCHES_DIR) {
+            Some(dir) => dir,
+            None => return 0,
+        };
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to read match store {}: {}", dir.display(), e);
+                return 0;
+            }
+        };
+
+        let mut loaded = HashMap::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            match read_json::<StoredMatch>(&path) {
+                Some(stored) => {
+                    loaded.insert(stored.data.metadata.match_id.clone(), Arc::new(stored));
+                }
+                None => warn!("Skipping unreadable stored match {}", path.display()),
+            }
+        }
+
+        let count = loaded.len();
+        *self.matches.write().unwrap() = loaded;
+        count
+    }
+
+    pub fn get(&self, match_id: &str) -> Option<Match> {
+        self.matches.read().unwrap().get(match_id).map(|s| s.data.clone())
+    }
+
+    pub fn contains(&self, match_id: &str) -> bool {
+        self.matches.read().unwrap().contains_key(match_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.matches.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn persist(stored: &StoredMatch) {
+        let match_id = &stored.data.metadata.match_id;
+        if let Some(path) = data_path(&format!("{}/{}.json", MATCHES_DIR, match_id)) {
+            if let Err(e) = write_json(&path, stored) {
+                warn!("{}", e);
+            }
+        }
+    }
+
+    /// Store a match in memory and persist it when a data directory is configured
+    pub fn insert(&self, data: Match) {
+        let _persisting = self.persisting.lock().unwrap_or_else(|e| e.into_inner());
+        let match_id = data.metadata.match_id.clone();
+        let stored = {
+            let mut matches = self.matches.write().unwrap();
+            let (timeline, rank_tier) = matches.get(&match_id)
+                .map(|existing| (existing.timeline.clone(), existing.rank_tier.clone()))
+                .unwrap_or((None, None));
+            let stored = Arc::new(StoredMatch {
+                data,
+                timeline,
+                rank_tier,
+                stored_at: chrono::Utc::now().timestamp(),
+            });
+            matches.insert(match_id, stored.clone());
+            stored
+        };
+        Self::persist(&stored);
+    }
+
+    pub fn timeline(&self, match_id: &str) -> Option<MatchTimeline> {
+        self.matches.read().unwrap().get(match_id).and_then(|s| s.timeline.clone())
+    }
+
+    /// Replace a stored match with `change`'s result and persist it after releasing the lock.
+    /// Returns false if the match is not stored; nothing changes when `change` returns `None`.
+    fn update(&self, match_id: &str, change: impl FnOnce(&StoredMatch) -> Option<StoredMatch>) -> bool {
+        let _persisting = self.persisting.lock().unwrap_or_else(|e| e.into_inner());
+        let updated = {
+            let mut matches = self.matches.write().unwrap();
+            let stored = match matches.get_mut(match_id) {
+                Some(stored) => stored,
+                None => return false,
+            };
+            match change(stored) {
+                Some(changed) => *stored = Arc::new(changed),
+                None => return true,
+            }
+            stored.clone()
+        };
+        Self::persist(&updated);
+        true
+    }
+
+    /// Attach a timeline to a stored match. Returns false if the match is not stored.
+    pub fn insert_timeline(&self, match_id: &str, timeline: MatchTimeline) -> bool {
+        self.update(match_id, |stored| Some(StoredMatch {
+            data: stored.data.clone(),
+            timeline: Some(timeline),
+            rank_tier: stored.rank_tier.clone(),
+            stored_at: stored.stored_at,
+        }))
+    }
+
+    /// Record the rank bucket for a stored match unless one is already set
+    pub fn tag_rank(&self, match_id: &str, tier: &str) {
+        self.update(match_id, |stored| match stored.rank_tier {
+            Some(_) => None,
+            None => Some(StoredMatch { rank_tier: Some(tier.to_uppercase()), ..stored.clone() }),
+        });
+    }
+
+    /// Stored matches with their timelines and rank tags, filtered by rank bucket, newest first.
+    /// Entries are shared with the store rather than copied.
+    pub fn stored_in_bucket(&self, filter: RankFilter) -> Vec<Arc<StoredMatch>> {
+        let mut stored: Vec<Arc<StoredMatch>> = self.matches.read().unwrap()
+            .values()
+            .filter(|s| filter.accepts(s.rank_tier.as_deref()))
+            .cloned()
+            .collect();
+        stored.sort_by_key(|s| std::cmp::Reverse(s.data.info.game_creation));
+        stored
+    }
+
+    /// All stored matches the player took part in, newest first
+    pub fn matches_for(&self, puuid: &str) -> Vec<Match> {
+        let mut matches: Vec<Match> = self.matches.read().unwrap()
+            .values()
+            .filter(|s| s.data.info.participants.iter().any(|p| p.puuid == puuid))
+            .map(|s| s.data.clone())
+            .collect();
+        matches.sort_by_key(|m| std::cmp::Reverse(m.info.game_creation));
+        matches
+    }
+
+    /// Run `f` over every stored match without cloning them, in no particular order
+    pub fn with_matches<R>(&self, f: impl FnOnce(&mut dyn Iterator<Item = &Match>) -> R) -> R {
+        let matches = self.matches.read().unwrap();
+        f(&mut matches.values().map(|s| &s.data))
+    }
+
+    /// Every stored match, newest first
+    pub fn all(&self) -> Vec<Match> {
+        let mut matches: Vec<Match> = self.matches.read().unwrap()
+            .values()
+            .map(|s| s.data.clone())
+            .collect();
+        matches.sort_by_key(|m| std::cmp::Reverse(m.info.game_creation));
+        matches
+    }
+}
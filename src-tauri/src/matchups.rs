@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::builds::role_to_position;
+use crate::match_analysis::{champion_id_of, find_lane_opponent, is_remake};
+use crate::match_store::StoredMatch;
+use crate::timeline::MatchTimeline;
+
+/// Minute at which lane differentials are sampled
+const DIFF_MINUTE: i64 = 15;
+
+/// Kills before this timestamp count as laning-phase kills
+const LANE_PHASE_END_MS: i64 = 14 * 60_000;
+
+/// Matchups with fewer games are reported but never classified as counters
+pub const MIN_MATCHUP_GAMES: u32 = 5;
+
+/// z-score for a 95% confidence interval
+const Z_95: f32 = 1.96;
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchupStats {
+    pub enemy_champion_id: i32,
+    pub enemy_champion_name: String,
+    pub role: Option<String>,
+    pub games: u32,
+    pub wins: u32,
+    pub win_rate: f32,
+    pub win_rate_low: f32,
+    pub win_rate_high: f32,
+    pub meets_sample_threshold: bool,
+    pub timeline_games: u32,
+    pub gold_diff15: Option<f32>,
+    pub cs_diff15: Option<f32>,
+    pub lane_kill_share: Option<f32>,
+    pub lane_death_share: Option<f32>,
+    pub average_kda: MatchupKda,
+    pub difficulty: String,
+    pub tips: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchupKda {
+    pub kills: f32,
+    pub deaths: f32,
+    pub assists: f32,
+}
+
+#[derive(Default)]
+struct MatchupAccumulator {
+    enemy_champion_name: String,
+    games: u32,
+    wins: u32,
+    kills: i32,
+    deaths: i32,
+    assists: i32,
+    timeline_games: u32,
+    gold_diff15: i64,
+    cs_diff15: i64,
+    lane_kills: u32,
+    lane_deaths: u32,
+    opponent_lane_kills: u32,
+    opponent_lane_deaths: u32,
+}
+
+/// Wilson score interval for a win rate, returned as percentages
+pub fn wilson_interval(wins: u32, games: u32) -> (f32, f32) {
+    if games == 0 {
+        return (0.0, 100.0);
+    }
+    let n = games as f32;
+    let p = wins as f32 / n;
+    let z2 = Z_95 * Z_95;
+    let denominator = 1.0 + z2 / n;
+    let centre = p + z2 / (2.0 * n);
+    let margin = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    (
+        ((centre - margin) / denominator * 100.0).max(0.0),
+        ((centre + margin) / denominator * 100.0).min(100.0),
+    )
+}
+
+fn difficulty_for(win_rate: f32) -> &'static str {
+    if win_rate < 42.0 {
+        "Very Hard"
+    } else if win_rate < 48.0 {
+        "Hard"
+    } else if win_rate <= 52.0 {
+        "Medium"
+    } else if win_rate <= 58.0 {
+        "Easy"
+    } else {
+        "Very Easy"
+    }
+}
+
+/// Laning-phase kills and deaths for a participant and their opponent
+fn lane_phase_kills(timeline: &MatchTimeline, player_id: i32, opponent_id: i32) -> (u32, u32, u32, u32) {
+    let mut counts = (0, 0, 0, 0);
+    for event in timeline.events_of_type("CHAMPION_KILL") {
+        if event.timestamp >= LANE_PHASE_END_MS {
+            continue;
+        }
+        if event.killer_id == Some(player_id) {
+            counts.0 += 1;
+        }
+        if event.victim_id == Some(player_id) {
+            counts.1 += 1;
+        }
+        if event.killer_id == Some(opponent_id) {
+            counts.2 += 1;
+        }
+        if event.victim_id == Some(opponent_id) {
+            counts.3 += 1;
+        }
+    }
+    counts
+}
+
+fn ratio(part: u32, other: u32) -> Option<f32> {
+    if part + other == 0 { None } else { Some(part as f32 / (part + other) as f32) }
+}
+
+fn matchup_tips(stats: &MatchupStats) -> Vec<String> {
+    let mut tips = Vec::new();
+    if let Some(gold) = stats.gold_diff15 {
+        if gold <= -300.0 {
+            tips.push(format!("You fall {:.0} gold behind by 15 minutes; prioritise safe farming over trades", -gold));
+        } else if gold >= 300.0 {
+            tips.push(format!("You average a {:.0} gold lead at 15 minutes; look to convert it into objectives", gold));
+        }
+    }
+    if let Some(cs) = stats.cs_diff15 {
+        if cs <= -8.0 {
+            tips.push(format!("Down {:.0} CS at 15 on average; consider a safer wave-clear setup", -cs));
+        }
+    }
+    if let Some(death_share) = stats.lane_death_share {
+        if death_share >= 0.6 {
+            tips.push("Most laning-phase deaths in this matchup are yours; respect their kill pressure before 14 minutes".to_string());
+        }
+    }
+    if let Some(kill_share) = stats.lane_kill_share {
+        if kill_share >= 0.6 {
+            tips.push("You win most laning-phase skirmishes here; play for early kills".to_string());
+        }
+    }
+    if !stats.meets_sample_threshold {
+        tips.push(format!("Only {} games recorded; treat these numbers as indicative", stats.games));
+    }
+    tips
+}
+
+/// Aggregate lane matchups for a champion against every opposing champion seen
+/// in the same `team_position`, sorted by games played
+pub fn aggregate_matchups(stored: &[Arc<StoredMatch>], champion_id: i32, role: Option<&str>) -> Vec<MatchupStats> {
+    let position = role.and_then(role_to_position);
+    let mut by_enemy: HashMap<i32, MatchupAccumulator> = HashMap::new();
+
+    for entry in stored.iter().filter(|entry| !is_remake(&entry.data)) {
+        let m = &entry.data;
+        for player in &m.info.participants {
+            if champion_id_of(player) != champion_id {
+                continue;
+            }
+            if position.is_some_and(|pos| player.team_position != pos) {
+                continue;
+            }
+            let opponent = match find_lane_opponent(m, player) {
+                Some(opponent) => opponent,
+                None => continue,
+            };
+
+            let acc = by_enemy.entry(champion_id_of(opponent)).or_default();
+            acc.enemy_champion_name = opponent.champion_name.clone();
+            acc.games += 1;
+            if player.win {
+                acc.wins += 1;
+            }
+            acc.kills += player.kills;
+            acc.deaths += player.deaths;
+            acc.assists += player.assists;
+
+            if let Some(timeline) = &entry.timeline {
+                if m.info.game_duration >= DIFF_MINUTE * 60 {
+                    let frames = (
+                        timeline.frame_at_minute(player.participant_id, DIFF_MINUTE),
+                        timeline.frame_at_minute(opponent.participant_id, DIFF_MINUTE),
+                    );
+                    if let (Some(mine), Some(theirs)) = frames {
+                        acc.timeline_games += 1;
+                        acc.gold_diff15 += (mine.total_gold - theirs.total_gold) as i64;
+                        acc.cs_diff15 += ((mine.minions_killed + mine.jungle_minions_killed)
+                            - (theirs.minions_killed + theirs.jungle_minions_killed)) as i64;
+                    }
+                }
+                let (kills, deaths, opponent_kills, opponent_deaths) =
+                    lane_phase_kills(timeline, player.participant_id, opponent.participant_id);
+                acc.lane_kills += kills;
+                acc.lane_deaths += deaths;
+                acc.opponent_lane_kills += opponent_kills;
+                acc.opponent_lane_deaths += opponent_deaths;
+            }
+        }
+    }
+
+    let mut matchups: Vec<MatchupStats> = by_enemy.into_iter()
+        .map(|(enemy_champion_id, acc)| {
+            let games = acc.games as f32;
+            let win_rate = acc.wins as f32 / games * 100.0;
+            let (win_rate_low, win_rate_high) = wilson_interval(acc.wins, acc.games);
+            let per_timeline_game = |total: i64| {
+                if acc.timeline_games > 0 { Some(total as f32 / acc.timeline_games as f32) } else { None }
+            };
+
+            let mut stats = MatchupStats {
+                enemy_champion_id,
+                enemy_champion_name: acc.enemy_champion_name.clone(),
+                role: position.map(|p| p.to_string()),
+                games: acc.games,
+                wins: acc.wins,
+                win_rate,
+                win_rate_low,
+                win_rate_high,
+                meets_sample_threshold: acc.games >= MIN_MATCHUP_GAMES,
+                timeline_games: acc.timeline_games,
+                gold_diff15: per_timeline_game(acc.gold_diff15),
+                cs_diff15: per_timeline_game(acc.cs_diff15),
+                lane_kill_share: ratio(acc.lane_kills, acc.opponent_lane_kills),
+                lane_death_share: ratio(acc.lane_deaths, acc.opponent_lane_deaths),
+                average_kda: MatchupKda {
+                    kills: acc.kills as f32 / games,
+                    deaths: acc.deaths as f32 / games,
+                    assists: acc.assists as f32 / games,
+                },
+                difficulty: difficulty_for(win_rate).to_string(),
+                tips: Vec::new(),
+            };
+            stats.tips = matchup_tips(&stats);
+            stats
+        })
+        .collect();
+
+    matchups.sort_by_key(|m| (std::cmp::Reverse(m.games), m.enemy_champion_id));
+    matchups
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterEntry {
+    pub champion_id: i32,
+    pub champion_name: String,
+    pub win_rate_against: f32,
+    pub win_rate_low: f32,
+    pub win_rate_high: f32,
+    pub games: u32,
+    pub difficulty: String,
+    pub reason: String,
+}
+
+#[derive(Debug, serde::Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterGroup {
+    pub hard_counters: Vec<CounterEntry>,
+    pub soft_counters: Vec<CounterEntry>,
+}
+
+/// Split matchups into counters (bad for the champion) and favourable matchups.
+/// "Hard" means the whole confidence interval sits on one side of 50%;
+/// "soft" means the point estimate does but the interval still crosses it.
+pub fn classify_counters(matchups: &[MatchupStats]) -> (CounterGroup, CounterGroup) {
+    let mut counters = CounterGroup::default();
+    let mut good_against = CounterGroup::default();
+
+    for m in matchups.iter().filter(|m| m.meets_sample_threshold) {
+        let entry = CounterEntry {
+            champion_id: m.enemy_champion_id,
+            champion_name: m.enemy_champion_name.clone(),
+            win_rate_against: m.win_rate,
+            win_rate_low: m.win_rate_low,
+            win_rate_high: m.win_rate_high,
+            games: m.games,
+            difficulty: m.difficulty.clone(),
+            reason: format!(
+                "{:.1}% win rate over {} games (95% CI {:.1}–{:.1}%)",
+                m.win_rate, m.games, m.win_rate_low, m.win_rate_high
+            ),
+        };
+        if m.win_rate_high < 50.0 {
+            counters.hard_counters.push(entry);
+        } else if m.win_rate_low > 50.0 {
+            good_against.hard_counters.push(entry);
+        } else if m.win_rate < 50.0 {
+            counters.soft_counters.push(entry);
+        } else if m.win_rate > 50.0 {
+            good_against.soft_counters.push(entry);
+        }
+    }
+
+    let by_win_rate = |a: &CounterEntry, b: &CounterEntry| a.win_rate_against.total_cmp(&b.win_rate_against);
+    counters.hard_counters.sort_by(by_win_rate);
+    counters.soft_counters.sort_by(by_win_rate);
+    good_against.hard_counters.sort_by(|a, b| by_win_rate(b, a));
+    good_against.soft_counters.sort_by(|a, b| by_win_rate(b, a));
+
+    (counters, good_against)
+}
+
+/// Share of stored games in which each champion was banned
+pub fn ban_rates(stored: &[Arc<StoredMatch>]) -> HashMap<i32, f32> {
+    let mut bans: HashMap<i32, u32> = HashMap::new();
+    for entry in stored {
+        for team in &entry.data.info.teams {
+            for ban in &team.bans {
+                let champion = ban.champion_id.0 as i32;
+                if champion > 0 {
+                    *bans.entry(champion).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+    let total = stored.len().max(1) as f32;
+    bans.into_iter()
+        .map(|(champion, count)| (champion, count as f32 / total * 100.0))
+        .collect()
+}
@@ -0,0 +1,141 @@
+use chrono::{Datelike, Timelike};
+
+use crate::riot_client::HistoricalMatchData;
+
+const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+/// Buckets need this many games before they are called out as strong or weak
+const MIN_FLAGGED_GAMES: u32 = 5;
+
+/// Win-rate gap (percentage points) from the overall rate that gets a bucket flagged
+const FLAG_WIN_RATE_GAP: f32 = 8.0;
+
+#[derive(Debug, serde::Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaytimeBucket {
+    pub label: String,
+    pub games: u32,
+    pub wins: u32,
+    pub win_rate: f32,
+    pub average_performance: Option<f32>,
+    pub average_game_length: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaytimeHeatmap {
+    pub games_analyzed: u32,
+    pub overall_win_rate: f32,
+    /// Offset of local time from UTC, in minutes, used for bucketing
+    pub utc_offset_minutes: i32,
+    pub by_hour: Vec<PlaytimeBucket>,
+    pub by_weekday: Vec<PlaytimeBucket>,
+    /// `grid[weekday][hour]`, Monday first
+    pub grid: Vec<Vec<PlaytimeBucket>>,
+    pub strongest_hours: Vec<PlaytimeBucket>,
+    pub weakest_hours: Vec<PlaytimeBucket>,
+}
+
+#[derive(Default)]
+struct BucketTotals {
+    games: u32,
+    wins: u32,
+    performance: Vec<f32>,
+    minutes: f32,
+}
+
+impl BucketTotals {
+    fn add(&mut self, m: &HistoricalMatchData) {
+        self.games += 1;
+        if m.participant_data.win {
+            self.wins += 1;
+        }
+        if let Some(score) = m.performance_score {
+            self.performance.push(score);
+        }
+        self.minutes += m.game_duration as f32 / 60.0;
+    }
+
+    fn finish(&self, label: String) -> PlaytimeBucket {
+        let games = self.games.max(1) as f32;
+        PlaytimeBucket {
+            label,
+            games: self.games,
+            wins: self.wins,
+            win_rate: self.wins as f32 / games * 100.0,
+            average_performance: if self.performance.is_empty() {
+                None
+            } else {
+                Some(self.performance.iter().sum::<f32>() / self.performance.len() as f32)
+            },
+            average_game_length: self.minutes / games,
+        }
+    }
+}
+
+fn hour_label(hour: usize) -> String {
+    format!("{:02}:00", hour)
+}
+
+/// Bucket games by local hour of day and weekday
+pub fn playtime_heatmap(matches: &[HistoricalMatchData]) -> PlaytimeHeatmap {
+    let mut hours: Vec<BucketTotals> = (0..24).map(|_| BucketTotals::default()).collect();
+    let mut weekdays: Vec<BucketTotals> = (0..7).map(|_| BucketTotals::default()).collect();
+    let mut grid: Vec<Vec<BucketTotals>> = (0..7)
+        .map(|_| (0..24).map(|_| BucketTotals::default()).collect())
+        .collect();
+    let mut overall = BucketTotals::default();
+
+    for m in matches {
+        let local = match chrono::DateTime::from_timestamp_millis(m.game_creation) {
+            Some(utc) => utc.with_timezone(&chrono::Local),
+            None => continue,
+        };
+        let hour = local.hour() as usize;
+        let weekday = local.weekday().num_days_from_monday() as usize;
+        hours[hour].add(m);
+        weekdays[weekday].add(m);
+        grid[weekday][hour].add(m);
+        overall.add(m);
+    }
+
+    let overall = overall.finish("overall".to_string());
+    let by_hour: Vec<PlaytimeBucket> = hours.iter()
+        .enumerate()
+        .map(|(hour, totals)| totals.finish(hour_label(hour)))
+        .collect();
+
+    let flagged: Vec<&PlaytimeBucket> = by_hour.iter().filter(|b| b.games >= MIN_FLAGGED_GAMES).collect();
+    let mut strongest_hours: Vec<PlaytimeBucket> = flagged.iter()
+        .filter(|b| b.win_rate - overall.win_rate >= FLAG_WIN_RATE_GAP)
+        .map(|b| (*b).clone())
+        .collect();
+    strongest_hours.sort_by(|a, b| b.win_rate.total_cmp(&a.win_rate));
+    let mut weakest_hours: Vec<PlaytimeBucket> = flagged.iter()
+        .filter(|b| overall.win_rate - b.win_rate >= FLAG_WIN_RATE_GAP)
+        .map(|b| (*b).clone())
+        .collect();
+    weakest_hours.sort_by(|a, b| a.win_rate.total_cmp(&b.win_rate));
+
+    PlaytimeHeatmap {
+        games_analyzed: overall.games,
+        overall_win_rate: overall.win_rate,
+        utc_offset_minutes: chrono::Local::now().offset().local_minus_utc() / 60,
+        by_weekday: weekdays.iter()
+            .enumerate()
+            .map(|(day, totals)| totals.finish(WEEKDAYS[day].to_string()))
+            .collect(),
+        grid: grid.iter()
+            .enumerate()
+            .map(|(day, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(hour, totals)| totals.finish(format!("{} {}", WEEKDAYS[day], hour_label(hour))))
+                    .collect()
+            })
+            .collect(),
+        by_hour,
+        strongest_hours,
+        weakest_hours,
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use riven::models::match_v5::Match;
+
+use crate::match_analysis::team_id_of;
+
+/// Same-team recent games two lobby members need before they are linked as premade
+const MIN_SHARED_GAMES: u32 = 2;
+
+/// One live participant: their current team and the team they were on in each recent match
+pub struct LobbyMember {
+    pub puuid: String,
+    pub team_id: i32,
+    pub recent_teams: HashMap<String, i32>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PremadeLink {
+    pub first: String,
+    pub second: String,
+    pub shared_games: u32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PremadeGroup {
+    pub team_id: i32,
+    pub members: Vec<String>,
+    /// Most same-team recent games between any two members
+    pub shared_games: u32,
+    pub links: Vec<PremadeLink>,
+    pub includes_tracked_player: bool,
+    /// On the opposing team from the tracked player
+    pub enemy: bool,
+}
+
+impl LobbyMember {
+    pub fn new(puuid: &str, team_id: i32, recent: &[Match]) -> Self {
+        let recent_teams = recent.iter()
+            .filter_map(|m| {
+                m.info.participants.iter()
+                    .find(|p| p.puuid == puuid)
+                    .map(|p| (m.metadata.match_id.clone(), team_id_of(p)))
+            })
+            .collect();
+        LobbyMember { puuid: puuid.to_string(), team_id, recent_teams }
+    }
+
+    /// Recent matches both players appear in on the same side
+    fn shared_with(&self, other: &LobbyMember) -> u32 {
+        self.recent_teams.iter()
+            .filter(|(match_id, team)| other.recent_teams.get(*match_id) == Some(team))
+            .count() as u32
+    }
+}
+
+/// Union-find root with path halving
+fn root(group_of: &mut [usize], mut i: usize) -> usize {
+    while group_of[i] != i {
+        group_of[i] = group_of[group_of[i]];
+        i = group_of[i];
+    }
+    i
+}
+
+/// Group current teammates who keep appearing on the same side in each other's recent games
+pub fn detect_premades(lobby: &[LobbyMember], tracked_puuid: &str) -> Vec<PremadeGroup> {
+    let tracked_team = lobby.iter().find(|m| m.puuid == tracked_puuid).map(|m| m.team_id);
+
+    let mut links = Vec::new();
+    for (i, first) in lobby.iter().enumerate() {
+        for (j, second) in lobby.iter().enumerate().skip(i + 1) {
+            if first.team_id != second.team_id || first.puuid.is_empty() || second.puuid.is_empty() {
+                continue;
+            }
+            let shared_games = first.shared_with(second);
+            if shared_games >= MIN_SHARED_GAMES {
+                links.push((i, j, shared_games));
+            }
+        }
+    }
+
+    // Connected components over the links
+    let mut group_of: Vec<usize> = (0..lobby.len()).collect();
+    for &(a, b, _) in &links {
+        let (ra, rb) = (root(&mut group_of, a), root(&mut group_of, b));
+        group_of[ra] = rb;
+    }
+
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &(a, b, _) in &links {
+        let r = root(&mut group_of, a);
+        let members = groups.entry(r).or_default();
+        for index in [a, b] {
+            if !members.contains(&index) {
+                members.push(index);
+            }
+        }
+    }
+
+    let mut premades: Vec<PremadeGroup> = groups.into_iter()
+        .map(|(r, mut members)| {
+            members.sort_unstable();
+            let group_links: Vec<PremadeLink> = links.iter()
+                .filter(|(a, _, _)| root(&mut group_of, *a) == r)
+                .map(|&(a, b, shared_games)| PremadeLink {
+                    first: lobby[a].puuid.clone(),
+                    second: lobby[b].puuid.clone(),
+                    shared_games,
+                })
+                .collect();
+            let team_id = lobby[members[0]].team_id;
+            PremadeGroup {
+                team_id,
+                includes_tracked_player: members.iter().any(|&i| lobby[i].puuid == tracked_puuid),
+                enemy: tracked_team.is_some_and(|team| team != team_id),
+                members: members.iter().map(|&i| lobby[i].puuid.clone()).collect(),
+                shared_games: group_links.iter().map(|l| l.shared_games).max().unwrap_or(0),
+                links: group_links,
+            }
+        })
+        .collect();
+    premades.sort_by_key(|g| std::cmp::Reverse(g.shared_games));
+    premades
+}
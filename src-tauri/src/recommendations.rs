@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use crate::match_analysis::role_benchmark;
+use crate::riot_client::HistoricalMatchData;
+
+/// Most recent games considered
+const RECENT_GAMES: usize = 30;
+
+/// A role needs this many recent games before it gets recommendations
+const MIN_ROLE_GAMES: usize = 5;
+
+/// Gaps smaller than this fraction of the target are not worth a recommendation
+const MIN_GAP: f32 = 0.05;
+
+/// Supporting games listed per recommendation
+const SUPPORTING_GAMES: usize = 5;
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Recommendation {
+    pub priority: u32,
+    pub severity: String,
+    pub role: String,
+    pub metric: String,
+    pub title: String,
+    pub advice: String,
+    pub current: f32,
+    pub target: f32,
+    /// Shortfall as a fraction of the target
+    pub gap: f32,
+    pub games_analyzed: u32,
+    /// Games furthest from the target, worst first
+    pub supporting_match_ids: Vec<String>,
+}
+
+/// One benchmarked metric: how to read it from a game and what good looks like for a role
+struct FocusArea {
+    metric: &'static str,
+    title: &'static str,
+    advice: &'static str,
+    /// Lower values are better
+    lower_is_better: bool,
+    target: fn(&str) -> Option<f32>,
+    value: fn(&HistoricalMatchData) -> Option<f32>,
+}
+
+fn minutes(m: &HistoricalMatchData) -> f32 {
+    (m.game_duration as f32 / 60.0).max(1.0)
+}
+
+const FOCUS_AREAS: [FocusArea; 7] = [
+    FocusArea {
+        metric: "csPerMinute",
+        title: "Improve farming",
+        advice: "Last-hit every wave before roaming and catch side waves between objectives",
+        lower_is_better: false,
+        target: |position| if position == "UTILITY" { None } else { Some(role_benchmark(position).cs_per_min) },
+        value: |m| Some((m.participant_data.total_minions_killed + m.participant_data.neutral_minions_killed) as f32 / minutes(m)),
+    },
+    FocusArea {
+        metric: "visionPerMinute",
+        title: "Contribute more vision",
+        advice: "Use your trinket on cooldown and sweep objective approaches before they spawn",
+        lower_is_better: false,
+        target: |position| Some(role_benchmark(position).vision_per_min),
+        value: |m| Some(m.participant_data.vision_score as f32 / minutes(m)),
+    },
+    FocusArea {
+        metric: "controlWards",
+        title: "Buy and place control wards",
+        advice: "Pick up a control ward on every back and place it in river or your jungle entrances",
+        lower_is_better: false,
+        target: |position| Some(match position {
+            "UTILITY" => 3.0,
+            "JUNGLE" => 2.0,
+            _ => 1.0,
+        }),
+        value: |m| Some(m.participant_data.detector_wards_placed as f32),
+    },
+    FocusArea {
+        metric: "deaths",
+        title: "Die less",
+        advice: "Review your deaths: most come from face-checking or staying too long after a wave is pushed",
+        lower_is_better: true,
+        target: |position| Some(if position == "UTILITY" { 6.0 } else { 5.0 }),
+        value: |m| Some(m.participant_data.deaths as f32),
+    },
+    FocusArea {
+        metric: "killParticipation",
+        title: "Join more fights",
+        advice: "Track where your team is grouping and move with them for dragons and skirmishes",
+        lower_is_better: false,
+        target: |position| Some(match position {
+            "JUNGLE" | "UTILITY" => 0.60,
+            _ => 0.50,
+        }),
+        value: |m| m.team_context.as_ref().map(|c| c.kill_participation),
+    },
+    FocusArea {
+        metric: "damageShare",
+        title: "Deal more damage in fights",
+        advice: "Look for safe windows to use your full combo rather than holding cooldowns",
+        lower_is_better: false,
+        target: |position| Some(role_benchmark(position).damage_share),
+        value: |m| m.team_context.as_ref().map(|c| c.damage_share),
+    },
+    FocusArea {
+        metric: "laneCsDiff",
+        title: "Win the farm in lane",
+        advice: "Trade only when the wave is on your side and don't miss cannon minions",
+        lower_is_better: false,
+        target: |position| match position {
+            "TOP" | "MIDDLE" | "BOTTOM" => Some(0.0),
+            _ => None,
+        },
+        value: |m| m.team_context.as_ref()
+            .and_then(|c| c.lane_opponent.as_ref())
+            .map(|o| o.cs_diff as f32),
+    },
+];
+
+/// Shortfall relative to the target; zero-valued targets use absolute units
+fn shortfall(current: f32, target: f32, lower_is_better: bool) -> f32 {
+    let missing = if lower_is_better { current - target } else { target - current };
+    if target.abs() > f32::EPSILON { missing / target.abs() } else { missing / 10.0 }
+}
+
+/// Prioritised focus areas from the player's recent games, grouped by role
+pub fn recommend(matches: &[HistoricalMatchData]) -> Vec<Recommendation> {
+    let mut recent: Vec<&HistoricalMatchData> = matches.iter().collect();
+    recent.sort_by_key(|m| std::cmp::Reverse(m.game_creation));
+    recent.truncate(RECENT_GAMES);
+
+    let mut by_role: HashMap<&str, Vec<&HistoricalMatchData>> = HashMap::new();
+    for m in &recent {
+        let position = m.participant_data.team_position.as_str();
+        if !position.is_empty() {
+            by_role.entry(position).or_default().push(m);
+        }
+    }
+
+    let mut recommendations = Vec::new();
+    for (position, games) in by_role {
+        if games.len() < MIN_ROLE_GAMES {
+            continue;
+        }
+        // Weight each role's gaps by how much of the player's recent time it covers
+        let role_weight = games.len() as f32 / recent.len() as f32;
+
+        for area in &FOCUS_AREAS {
+            let target = match (area.target)(position) {
+                Some(target) => target,
+                None => continue,
+            };
+            let mut values: Vec<(&str, f32)> = games.iter()
+                .filter_map(|m| (area.value)(m).map(|v| (m.match_id.as_str(), v)))
+                .collect();
+            if values.len() < MIN_ROLE_GAMES {
+                continue;
+            }
+            let current = values.iter().map(|(_, v)| v).sum::<f32>() / values.len() as f32;
+            let gap = shortfall(current, target, area.lower_is_better);
+            if gap < MIN_GAP {
+                continue;
+            }
+
+            values.sort_by(|a, b| {
+                shortfall(b.1, target, area.lower_is_better).total_cmp(&shortfall(a.1, target, area.lower_is_better))
+            });
+            recommendations.push((gap * role_weight, Recommendation {
+                priority: 0,
+                severity: if gap >= 0.3 { "high" } else if gap >= 0.15 { "medium" } else { "low" }.to_string(),
+                role: position.to_string(),
+                metric: area.metric.to_string(),
+                title: area.title.to_string(),
+                advice: area.advice.to_string(),
+                current,
+                target,
+                gap,
+                games_analyzed: values.len() as u32,
+                supporting_match_ids: values.iter()
+                    .filter(|(_, v)| shortfall(*v, target, area.lower_is_better) > 0.0)
+                    .take(SUPPORTING_GAMES)
+                    .map(|(id, _)| id.to_string())
+                    .collect(),
+            }));
+        }
+    }
+
+    recommendations.sort_by(|a, b| b.0.total_cmp(&a.0));
+    recommendations.into_iter()
+        .enumerate()
+        .map(|(index, (_, mut rec))| {
+            rec.priority = index as u32 + 1;
+            rec
+        })
+        .collect()
+}
@@ -0,0 +1,453 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use log::warn;
+
+use crate::match_store::MatchStore;
+use crate::storage::{data_path, read_json, write_json};
+
+const SETTINGS_FILE: &str = "replays/settings.json";
+const INDEX_FILE: &str = "replays/index.json";
+
+/// "RIOT" followed by two zero bytes
+const ROFL_MAGIC: &[u8; 6] = b"RIOT\0\0";
+
+/// Magic, then a 256-byte signature, then the header lengths and offsets
+const ROFL_HEADER_OFFSET: u64 = 6 + 256;
+
+/// Newer replays append the metadata JSON and its length to the end of the file instead
+const ROFL2_MAGIC: &[u8; 5] = b"RIOT2";
+
+/// Metadata blocks larger than this are treated as corrupt
+const MAX_METADATA_LEN: u32 = 4 * 1024 * 1024;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaySettings {
+    pub folder: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayPlayer {
+    pub name: String,
+    pub puuid: Option<String>,
+    pub champion: String,
+    pub team_id: i32,
+    pub position: String,
+    pub win: bool,
+    pub level: i32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub creep_score: i32,
+    pub gold_earned: i32,
+    pub damage_to_champions: i32,
+    pub vision_score: i32,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayInfo {
+    pub file_name: String,
+    pub path: String,
+    /// File modification time in epoch seconds; unchanged files aren't re-parsed
+    pub modified: i64,
+    pub format_version: u8,
+    pub game_id: Option<u64>,
+    pub platform: Option<String>,
+    /// match-v5 id (`EUW1_1234567890`) when the platform and game id are known
+    pub match_id: Option<String>,
+    pub game_length_ms: u64,
+    pub game_version: String,
+    pub players: Vec<ReplayPlayer>,
+}
+
+/// A library entry with whether the match is also in the local store
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayEntry {
+    #[serde(flatten)]
+    pub replay: ReplayInfo,
+    pub linked: bool,
+}
+
+/// The tracked player's stats from a replay of a match that isn't stored
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedGame {
+    pub file_name: String,
+    pub match_id: Option<String>,
+    pub modified: i64,
+    pub game_version: String,
+    pub game_length_ms: u64,
+    pub player: ReplayPlayer,
+}
+
+/// The JSON metadata block shared by both replay formats
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RoflMetadata {
+    #[serde(default)]
+    game_length: u64,
+    #[serde(default)]
+    game_version: String,
+    /// A JSON array of per-player string maps, itself encoded as a string
+    #[serde(default)]
+    stats_json: String,
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+fn read_exact_at<R: Read + Seek>(reader: &mut R, offset: u64, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut buffer = vec![0u8; len];
+    reader.seek(SeekFrom::Start(offset))?;
+    reader.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// File modification time in epoch seconds, 0 when the platform doesn't report one
+fn modified_secs(metadata: &std::fs::Metadata) -> i64 {
+    metadata.modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// "EUW1-1234567890.rofl" -> ("EUW1", 1234567890)
+fn ids_from_file_name(file_name: &str) -> (Option<String>, Option<u64>) {
+    let stem = file_name.trim_end_matches(".rofl");
+    match stem.split_once(['-', '_']) {
+        Some((platform, id)) => (Some(platform.to_uppercase()), id.parse().ok()),
+        None => (None, None),
+    }
+}
+
+fn stat(stats: &HashMap<String, String>, keys: &[&str]) -> i32 {
+    keys.iter()
+        .find_map(|key| stats.get(*key))
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
+fn text(stats: &HashMap<String, String>, keys: &[&str]) -> String {
+    keys.iter().find_map(|key| stats.get(*key)).cloned().unwrap_or_default()
+}
+
+fn parse_players(stats_json: &str) -> Vec<ReplayPlayer> {
+    let rows: Vec<HashMap<String, String>> = match serde_json::from_str(stats_json) {
+        Ok(rows) => rows,
+        Err(e) => {
+            warn!("Replay stats block is not a player list: {}", e);
+            return Vec::new();
+        }
+    };
+    rows.iter()
+        .map(|s| {
+            let game_name = text(s, &["RIOT_ID_GAME_NAME", "NAME"]);
+            let tag = text(s, &["RIOT_ID_TAG_LINE"]);
+            ReplayPlayer {
+                name: if tag.is_empty() { game_name } else { format!("{}#{}", game_name, tag) },
+                puuid: s.get("PUUID").cloned().filter(|p| !p.is_empty()),
+                champion: text(s, &["SKIN"]),
+                team_id: stat(s, &["TEAM"]),
+                position: text(s, &["TEAM_POSITION", "INDIVIDUAL_POSITION"]),
+                win: text(s, &["WIN"]) == "Win",
+                level: stat(s, &["LEVEL"]),
+                kills: stat(s, &["CHAMPIONS_KILLED"]),
+                deaths: stat(s, &["NUM_DEATHS"]),
+                assists: stat(s, &["ASSISTS"]),
+                creep_score: stat(s, &["MINIONS_KILLED"]) + stat(s, &["NEUTRAL_MINIONS_KILLED"]),
+                gold_earned: stat(s, &["GOLD_EARNED"]),
+                damage_to_champions: stat(s, &["TOTAL_DAMAGE_DEALT_TO_CHAMPIONS"]),
+                vision_score: stat(s, &["VISION_SCORE"]),
+            }
+        })
+        .collect()
+}
+
+/// Parse a `.rofl` file's header and metadata block. Supports the original format (metadata
+/// located via the header) and the newer one (metadata and its length at the end of the file).
+pub fn parse_rofl(path: &Path) -> Result<ReplayInfo, String> {
+    let io_err = |e: std::io::Error| format!("Failed to read {}: {}", path.display(), e);
+    let mut file = File::open(path).map_err(io_err)?;
+    let metadata = file.metadata().map_err(io_err)?;
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let mut replay = parse_replay(&mut file, metadata.len(), &file_name)?;
+    replay.path = path.display().to_string();
+    replay.modified = modified_secs(&metadata);
+    Ok(replay)
+}
+
+/// Parse replay bytes from `reader`; the caller fills in `path` and `modified`
+fn parse_replay<R: Read + Seek>(reader: &mut R, file_len: u64, file_name: &str) -> Result<ReplayInfo, String> {
+    let io_err = |e: std::io::Error| format!("Failed to read {}: {}", file_name, e);
+    let magic = read_exact_at(reader, 0, ROFL_MAGIC.len()).map_err(io_err)?;
+    let (platform, file_game_id) = ids_from_file_name(file_name);
+
+    let (format_version, metadata, header_game_id) = if magic.as_slice() == ROFL_MAGIC {
+        // header length u16, file length, metadata offset/length, payload header offset/length, payload offset
+        let header = read_exact_at(reader, ROFL_HEADER_OFFSET, 26).map_err(io_err)?;
+        let (metadata_offset, metadata_len) = (read_u32(&header, 6), read_u32(&header, 10));
+        let (payload_header_offset, payload_header_len) = (read_u32(&header, 14), read_u32(&header, 18));
+        if metadata_len > MAX_METADATA_LEN || metadata_offset as u64 + metadata_len as u64 > file_len {
+            return Err(format!("{} has a corrupt header (header length {})", file_name, read_u16(&header, 0)));
+        }
+        let metadata = read_exact_at(reader, metadata_offset as u64, metadata_len as usize).map_err(io_err)?;
+        let game_id = if payload_header_len >= 8 {
+            read_exact_at(reader, payload_header_offset as u64, 8)
+                .ok()
+                .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap_or([0; 8])))
+        } else {
+            None
+        };
+        (1, metadata, game_id)
+    } else if magic.starts_with(ROFL2_MAGIC) {
+        if file_len < 4 {
+            return Err(format!("{} is truncated", file_name));
+        }
+        let len_bytes = read_exact_at(reader, file_len - 4, 4).map_err(io_err)?;
+        let metadata_len = read_u32(&len_bytes, 0);
+        if metadata_len > MAX_METADATA_LEN || metadata_len as u64 + 4 > file_len {
+            return Err(format!("{} has a corrupt metadata length", file_name));
+        }
+        let metadata = read_exact_at(reader, file_len - 4 - metadata_len as u64, metadata_len as usize)
+            .map_err(io_err)?;
+        (2, metadata, None)
+    } else {
+        return Err(format!("{} is not a League replay", file_name));
+    };
+
+    let metadata: RoflMetadata = serde_json::from_slice(&metadata)
+        .map_err(|e| format!("Failed to parse metadata in {}: {}", file_name, e))?;
+
+    let game_id = header_game_id.filter(|id| *id > 0).or(file_game_id);
+    Ok(ReplayInfo {
+        path: String::new(),
+        modified: 0,
+        format_version,
+        match_id: match (&platform, game_id) {
+            (Some(platform), Some(id)) => Some(format!("{}_{}", platform, id)),
+            _ => None,
+        },
+        game_id,
+        platform,
+        game_length_ms: metadata.game_length,
+        game_version: metadata.game_version,
+        players: parse_players(&metadata.stats_json),
+        file_name: file_name.to_string(),
+    })
+}
+
+pub fn load_settings() -> ReplaySettings {
+    data_path(SETTINGS_FILE)
+        .and_then(|path| read_json(&path))
+        .unwrap_or_default()
+}
+
+pub fn save_settings(settings: &ReplaySettings) -> Result<(), String> {
+    let path = data_path(SETTINGS_FILE).ok_or("No data directory available for replay settings")?;
+    write_json(&path, settings)
+}
+
+/// The configured replays folder, or the client's default under Documents
+pub fn replay_folder() -> Option<PathBuf> {
+    if let Some(folder) = load_settings().folder.filter(|f| !f.trim().is_empty()) {
+        return Some(PathBuf::from(folder));
+    }
+    std::env::var_os("USERPROFILE")
+        .or_else(|| std::env::var_os("HOME"))
+        .map(|home| Path::new(&home).join("Documents").join("League of Legends").join("Replays"))
+}
+
+pub fn load_index() -> HashMap<String, ReplayInfo> {
+    data_path(INDEX_FILE)
+        .and_then(|path| read_json(&path))
+        .unwrap_or_default()
+}
+
+/// Parse new or changed replays in `folder` and drop entries whose files are gone
+pub fn scan_folder(folder: &Path) -> Result<HashMap<String, ReplayInfo>, String> {
+    let entries = std::fs::read_dir(folder)
+        .map_err(|e| format!("Failed to read replay folder {}: {}", folder.display(), e))?;
+    let previous = load_index();
+    let mut index = HashMap::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("rofl") {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let modified = entry.metadata().map(|m| modified_secs(&m)).unwrap_or(0);
+        if let Some(known) = previous.get(&file_name).filter(|r| r.modified == modified) {
+            index.insert(file_name, known.clone());
+            continue;
+        }
+        match parse_rofl(&path) {
+            Ok(replay) => {
+                index.insert(file_name, replay);
+            }
+            Err(e) => warn!("Skipping replay: {}", e),
+        }
+    }
+
+    let path = data_path(INDEX_FILE).ok_or("No data directory available for the replay index")?;
+    write_json(&path, &index)?;
+    Ok(index)
+}
+
+/// Library entries, newest first, flagged when the match is also stored
+pub fn library(index: &HashMap<String, ReplayInfo>, store: &MatchStore) -> Vec<ReplayEntry> {
+    let mut entries: Vec<ReplayEntry> = index.values()
+        .map(|replay| ReplayEntry {
+            linked: replay.match_id.as_deref().is_some_and(|id| store.contains(id)),
+            replay: replay.clone(),
+        })
+        .collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.replay.modified));
+    entries
+}
+
+/// The player's own line from each replay whose match the API can no longer provide
+pub fn imported_games(
+    index: &HashMap<String, ReplayInfo>,
+    store: &MatchStore,
+    puuid: &str,
+    riot_id: Option<&str>,
+) -> Vec<ImportedGame> {
+    let mut games: Vec<ImportedGame> = index.values()
+        .filter(|replay| !replay.match_id.as_deref().is_some_and(|id| store.contains(id)))
+        .filter_map(|replay| {
+            replay.players.iter()
+                .find(|p| {
+                    p.puuid.as_deref() == Some(puuid)
+                        || riot_id.is_some_and(|id| p.name.eq_ignore_ascii_case(id))
+                })
+                .map(|player| ImportedGame {
+                    file_name: replay.file_name.clone(),
+                    match_id: replay.match_id.clone(),
+                    modified: replay.modified,
+                    game_version: replay.game_version.clone(),
+                    game_length_ms: replay.game_length_ms,
+                    player: player.clone(),
+                })
+        })
+        .collect();
+    games.sort_by_key(|g| std::cmp::Reverse(g.modified));
+    games
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn metadata_json() -> Vec<u8> {
+        let players = serde_json::json!([{
+            "RIOT_ID_GAME_NAME": "Tester",
+            "RIOT_ID_TAG_LINE": "EUW",
+            "SKIN": "Ahri",
+            "TEAM": "100",
+            "WIN": "Win",
+            "CHAMPIONS_KILLED": "7",
+            "NUM_DEATHS": "2",
+            "MINIONS_KILLED": "150",
+            "NEUTRAL_MINIONS_KILLED": "10",
+        }]);
+        serde_json::to_vec(&serde_json::json!({
+            "gameLength": 1_800_000,
+            "gameVersion": "14.10.1",
+            "statsJson": players.to_string(),
+        }))
+        .unwrap()
+    }
+
+    /// Original format: magic, signature, header, metadata, then an 8-byte game id in the payload header
+    fn legacy_replay(metadata: &[u8], metadata_len: u32, game_id: u64) -> Vec<u8> {
+        let metadata_offset = ROFL_HEADER_OFFSET as u32 + 26;
+        let payload_header_offset = metadata_offset + metadata.len() as u32;
+        let mut bytes = ROFL_MAGIC.to_vec();
+        bytes.extend([0u8; 256]);
+        bytes.extend(26u16.to_le_bytes());
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend(metadata_offset.to_le_bytes());
+        bytes.extend(metadata_len.to_le_bytes());
+        bytes.extend(payload_header_offset.to_le_bytes());
+        bytes.extend(8u32.to_le_bytes());
+        bytes.extend((payload_header_offset + 8).to_le_bytes());
+        bytes.extend(metadata);
+        bytes.extend(game_id.to_le_bytes());
+        bytes
+    }
+
+    /// Newer format: magic and payload, then the metadata and its length at the end
+    fn riot2_replay(metadata: &[u8], metadata_len: u32) -> Vec<u8> {
+        let mut bytes = ROFL2_MAGIC.to_vec();
+        bytes.extend([0u8; 64]);
+        bytes.extend(metadata);
+        bytes.extend(metadata_len.to_le_bytes());
+        bytes
+    }
+
+    fn parse(bytes: &[u8], file_name: &str) -> Result<ReplayInfo, String> {
+        parse_replay(&mut Cursor::new(bytes), bytes.len() as u64, file_name)
+    }
+
+    #[test]
+    fn parses_legacy_header() {
+        let metadata = metadata_json();
+        let replay = parse(&legacy_replay(&metadata, metadata.len() as u32, 5_678), "EUW1-1234.rofl").unwrap();
+        assert_eq!(replay.format_version, 1);
+        assert_eq!(replay.game_id, Some(5_678));
+        assert_eq!(replay.match_id.as_deref(), Some("EUW1_5678"));
+        assert_eq!(replay.game_length_ms, 1_800_000);
+        assert_eq!(replay.game_version, "14.10.1");
+        assert_eq!(replay.players.len(), 1);
+        assert_eq!(replay.players[0].name, "Tester#EUW");
+        assert_eq!(replay.players[0].creep_score, 160);
+        assert!(replay.players[0].win);
+    }
+
+    #[test]
+    fn parses_riot2_trailer() {
+        let metadata = metadata_json();
+        let replay = parse(&riot2_replay(&metadata, metadata.len() as u32), "NA1-4321.rofl").unwrap();
+        assert_eq!(replay.format_version, 2);
+        assert_eq!(replay.match_id.as_deref(), Some("NA1_4321"));
+        assert_eq!(replay.players[0].kills, 7);
+    }
+
+    #[test]
+    fn rejects_truncated_file() {
+        let metadata = metadata_json();
+        let bytes = legacy_replay(&metadata, metadata.len() as u32, 5_678);
+        assert!(parse(&bytes[..100], "EUW1-1234.rofl").is_err());
+        assert!(parse(&bytes[..ROFL_HEADER_OFFSET as usize + 26 + 10], "EUW1-1234.rofl").is_err());
+        assert!(parse(b"RIOT", "EUW1-1234.rofl").is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_metadata_length() {
+        let metadata = metadata_json();
+        let error = parse(&riot2_replay(&metadata, MAX_METADATA_LEN + 1), "NA1-4321.rofl").unwrap_err();
+        assert!(error.contains("corrupt metadata length"), "{}", error);
+        let error = parse(&legacy_replay(&metadata, MAX_METADATA_LEN + 1, 5_678), "EUW1-1234.rofl").unwrap_err();
+        assert!(error.contains("corrupt header"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(parse(b"PK\x03\x04 not a replay", "notes.rofl").is_err());
+    }
+}
@@ -7,9 +7,26 @@ use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::time::{sleep, Duration};
 use crate::retry::{retry_riot_api, standard_retry_config, quick_retry_config};
+use crate::match_analysis::{
+    compute_team_context, phase_averages, player_score, score_match, team_id_of, PerformanceScore, TeamContext,
+};
+use crate::match_store::MatchStore;
+use crate::timeline::MatchTimeline;
+use crate::trends::analyze_trends;
+use crate::vision::{analyze_game_vision, vision_control_rating, GameVision};
 
 pub struct RiotClient {
     api: RiotApi,
+    store: MatchStore,
+}
+
+/// Query for one page of match-v5 IDs
+#[derive(Debug, Clone, Default)]
+pub struct MatchIdPage {
+    pub start: i32,
+    pub count: i32,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -117,11 +134,49 @@ pub struct HistoricalMatchData {
     pub match_id: String,
     pub game_creation: i64,
     pub game_duration: i64,
+    pub queue_id: i32,
     pub participant_data: ParticipantData,
+    /// Every player in the match, including the tracked one
+    pub participants: Vec<ParticipantRef>,
+    pub team_context: Option<TeamContext>,
+    pub performance_score: Option<f32>,
+    pub performance_ranking: Vec<PerformanceScore>,
     pub analytics_calculated: bool,
     pub cached_timestamp: i64,
 }
 
+/// Identity and headline stats of one player in a stored match
+#[derive(Debug, serde::Serialize, Clone)]
+pub struct ParticipantRef {
+    pub puuid: String,
+    pub riot_id_name: String,
+    pub riot_id_tagline: String,
+    pub champion_id: i32,
+    pub champion_name: String,
+    pub team_id: i32,
+    pub team_position: String,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub win: bool,
+}
+
+fn participant_ref(participant: &riven::models::match_v5::Participant) -> ParticipantRef {
+    ParticipantRef {
+        puuid: participant.puuid.clone(),
+        riot_id_name: participant.riot_id_name.clone().unwrap_or_else(|| participant.summoner_name.clone()),
+        riot_id_tagline: participant.riot_id_tagline.clone().unwrap_or_default(),
+        champion_id: participant.champion().map(|c| c.0 as i32).unwrap_or(0),
+        champion_name: participant.champion_name.clone(),
+        team_id: team_id_of(participant),
+        team_position: participant.team_position.clone(),
+        kills: participant.kills,
+        deaths: participant.deaths,
+        assists: participant.assists,
+        win: participant.win,
+    }
+}
+
 #[derive(Debug, serde::Serialize, Clone)]
 pub struct ParticipantData {
     pub assists: i32,
@@ -248,7 +303,7 @@ impl RiotClient {
         let api = RiotApi::new(&api_key);
         println!("RiotClient::new - Step D: RiotApi created successfully");
         
-        let client = Self { api };
+        let client = Self { api, store: MatchStore::default() };
         println!("RiotClient::new - Step E: RiotClient struct created");
         
         client
@@ -258,6 +313,11 @@ impl RiotClient {
         PlatformRoute::from_str(region).expect("invalid region")
     }
 
+    /// Local corpus of every match this client has fetched
+    pub fn store(&self) -> &MatchStore {
+        &self.store
+    }
+
     // Enhanced methods with retry logic
     pub async fn get_account_by_riot_id_with_retry(
         &self,
@@ -420,46 +480,39 @@ impl RiotClient {
             .await
     }
 
-    pub async fn calculate_traits(
+    pub async fn get_champion_mastery(
         &self,
         puuid: &str,
+        champion_id: i32,
         region: &str,
-    ) -> Result<Vec<String>, RiotApiError> {
-        let platform = Self::parse_region(region);
-        let route = platform.to_regional();
-
-        let ids = self
-            .api
-            .match_v5()
-            .get_match_ids_by_puuid(route, puuid, Some(5), None, None, None, None, None)
-            .await?;
-
-        let mut vision = 0.0f32;
-        let mut pentakill = false;
-        let mut count = 0u32;
-
-        for id in ids {
-            if let Some(m) = self.api.match_v5().get_match(route, &id).await? {
-                if let Some(p) = m.info.participants.iter().find(|p| p.puuid == puuid) {
-                    count += 1;
-                    vision += p.vision_score as f32;
-                    if p.penta_kills > 0 {
-                        pentakill = true;
-                    }
-                }
-            }
-        }
+    ) -> Result<Option<riven::models::champion_mastery_v4::ChampionMastery>, RiotApiError>
+    {
+        let route = Self::parse_region(region);
+        let path = format!("/lol/champion-mastery/v4/champion-masteries/by-puuid/{}/by-champion/{}", puuid, champion_id);
+        let req = self.api.request(Method::GET, route.into(), &path);
+        self.api
+            .execute_opt("champion-mastery-v4.getChampionMasteryByPUUID", route.into(), req)
+            .await
+    }
 
+    /// Full details of any player's most recent matches, newest first. Games already in the
+    /// store are read from it, but fetched ones are not added: other players' history would
+    /// otherwise leak into the tracked player's corpus.
+    pub async fn get_recent_match_details(
+        &self,
+        puuid: &str,
+        region: &str,
+        count: usize,
+    ) -> Result<Vec<riven::models::match_v5::Match>, RiotApiError> {
+        let ids = self.get_match_history_ids(puuid, region, count as i32).await?;
         let mut out = Vec::new();
-        if count > 0 {
-            if vision / (count as f32) < 20.0 {
-                out.push("Bad Vision".to_string());
-            }
-            if pentakill {
-                out.push("Clutch Finisher".to_string());
-            }
+        for id in ids {
+            let m = match self.store.get(&id) {
+                Some(stored) => Some(stored),
+                None => self.get_match_details(&id, region).await?,
+            };
+            out.extend(m);
         }
-
         Ok(out)
     }
 
@@ -480,7 +533,7 @@ impl RiotClient {
 
         let mut out = Vec::new();
         for id in ids {
-            if let Some(m) = self.api.match_v5().get_match(route, &id).await? {
+            if let Some(m) = self.get_match_cached(&id, region).await? {
                 if let Some(p) = m.info.participants.iter().find(|p| p.puuid == puuid) {
                     let champ_id = p
                         .champion()
@@ -518,41 +571,23 @@ impl RiotClient {
             .get_match_ids_by_puuid(route, puuid, Some(match_count as i32), None, None, None, None, None)
             .await?;
 
-        let mut total_early_performance = 0.0f32;
-        let mut total_mid_performance = 0.0f32;
-        let mut total_late_performance = 0.0f32;
+        let mut scored_games: Vec<(i64, f32)> = Vec::new();
         let mut total_objective_control = 0.0f32;
         let mut total_vision_score = 0.0f32;
+        let mut team_contexts: Vec<TeamContext> = Vec::new();
+        let mut vision_games: Vec<GameVision> = Vec::new();
         let mut analyzed_matches = 0u32;
 
         // Analyze each match for advanced metrics
         for match_id in match_ids.iter().take(match_count) {
-            if let Some(match_data) = self.api.match_v5().get_match(route, match_id).await? {
+            if let Some(match_data) = self.get_match_cached(match_id, region).await? {
                 if let Some(participant) = match_data.info.participants.iter().find(|p| p.puuid == puuid) {
                     analyzed_matches += 1;
 
-                    // Calculate game phase performance (simplified)
-                    let game_duration = match_data.info.game_duration;
-                    let kills_per_min = participant.kills as f32 / (game_duration as f32 / 60.0);
-                    let damage_per_min = participant.total_damage_dealt_to_champions as f32 / (game_duration as f32 / 60.0);
-                    
-                    // Early game performance (first 15 minutes equivalent)
-                    let early_performance = (kills_per_min * 0.3 + damage_per_min / 1000.0 * 0.7).min(10.0);
-                    total_early_performance += early_performance;
-
-                    // Mid game performance 
-                    let mid_performance = (participant.assists as f32 / 10.0 + 
-                                         participant.total_damage_dealt_to_champions as f32 / 15000.0).min(10.0);
-                    total_mid_performance += mid_performance;
-
-                    // Late game performance
-                    let late_performance = if game_duration > 1800 { // 30+ minutes
-                        (participant.total_damage_dealt_to_champions as f32 / 20000.0 + 
-                         if participant.win { 3.0 } else { 0.0 }).min(10.0)
-                    } else {
-                        5.0 // Default for shorter games
-                    };
-                    total_late_performance += late_performance;
+                    // The game's composite score feeds the phase in which the game was decided
+                    if let Some(score) = player_score(&match_data, puuid) {
+                        scored_games.push((match_data.info.game_duration, score));
+                    }
 
                     // Objective control
                     let objective_score = (participant.vision_score as f32 / 25.0 +
@@ -560,24 +595,53 @@ impl RiotClient {
                     total_objective_control += objective_score;
 
                     total_vision_score += participant.vision_score as f32;
+
+                    // Ward and epic monster events from the timeline; a missing timeline only
+                    // loses the vision rating and falls back to last hits for objectives
+                    let timeline = match self.get_timeline_cached(match_id, region).await {
+                        Ok(timeline) => timeline,
+                        Err(e) => {
+                            log::warn!("Failed to fetch timeline for {}: {:?}", match_id, e);
+                            None
+                        }
+                    };
+                    if let Some(timeline) = &timeline {
+                        vision_games.extend(analyze_game_vision(&match_data, timeline, puuid));
+                    }
+
+                    if let Some(context) = compute_team_context(&match_data, puuid, timeline.as_ref()) {
+                        team_contexts.push(context);
+                    }
                 }
             }
         }
 
-        let avg_early = if analyzed_matches > 0 { total_early_performance / analyzed_matches as f32 } else { 5.0 };
-        let avg_mid = if analyzed_matches > 0 { total_mid_performance / analyzed_matches as f32 } else { 5.0 };
-        let avg_late = if analyzed_matches > 0 { total_late_performance / analyzed_matches as f32 } else { 5.0 };
+        let [avg_early, avg_mid, avg_late] = phase_averages(&scored_games);
         let avg_vision = if analyzed_matches > 0 { total_vision_score / analyzed_matches as f32 } else { 20.0 };
 
-        // Calculate scaling effectiveness
-        let scaling_effectiveness = if avg_early > 0.0 { avg_late / avg_early } else { 1.0 };
+        // Average the team-relative objective involvement across analyzed games
+        let context_avg = |f: fn(&TeamContext) -> f32| -> f32 {
+            if team_contexts.is_empty() {
+                0.0
+            } else {
+                team_contexts.iter().map(f).sum::<f32>() / team_contexts.len() as f32
+            }
+        };
+
+        // Scaling and adaptation come from the player's full stored history when it is long enough
+        let stored_matches = self.store.matches_for(puuid);
+        let trends = analyze_trends(&stored_matches, puuid);
+        let scaling_effectiveness = trends.scaling_effectiveness.unwrap_or_else(|| {
+            let ratio = if avg_early > 0.0 { avg_late / avg_early } else { 1.0 };
+            (ratio * 50.0).min(100.0)
+        });
 
         Ok(AdvancedMatchAnalysis {
             game_phase_performance: GamePhasePerformance {
                 early_game_rating: (avg_early * 10.0).min(100.0),
                 mid_game_rating: (avg_mid * 10.0).min(100.0),
                 late_game_rating: (avg_late * 10.0).min(100.0),
-                scaling_effectiveness: (scaling_effectiveness * 50.0).min(100.0),
+                scaling_effectiveness,
             },
             champion_mastery_context: ChampionMasteryContext {
                 mastery_level: 0, // Would need champion mastery API call
@@ -591,14 +655,15 @@ impl RiotClient {
                 risk_tolerance: 70.0 + (avg_early - 5.0) * 10.0,
                 team_fight_participation: (avg_mid * 9.0).min(100.0),
                 objective_prioritization: (total_objective_control / analyzed_matches as f32 * 10.0).min(100.0),
-                adaptation_speed: 75.0, // Mock - would calculate from meta changes
+                adaptation_speed: trends.adaptation_speed.unwrap_or(50.0),
             },
             objective_control: ObjectiveControl {
-                dragon_participation: 0.75, // Mock data - would need timeline data
-                baron_participation: 0.68,
-                tower_damage_share: 0.22,
-                vision_control_rating: (avg_vision / 40.0 * 100.0).min(100.0),
-                jungle_control: 0.45,
+                dragon_participation: context_avg(|c| c.objective_participation.dragon_participation),
+                baron_participation: context_avg(|c| c.objective_participation.baron_participation),
+                tower_damage_share: context_avg(|c| c.tower_damage_share),
+                vision_control_rating: vision_control_rating(&vision_games)
+                    .unwrap_or((avg_vision / 40.0 * 100.0).min(100.0)),
+                jungle_control: context_avg(|c| c.jungle_control),
             },
         })
     }
@@ -618,112 +683,26 @@ impl RiotClient {
             .get_match_ids_by_puuid(route, puuid, Some(10), None, None, None, None, None)
             .await?;
 
-        let mut total_kills = 0i32;
-        let mut total_deaths = 0i32;
-        let mut total_assists = 0i32;
-        let mut total_vision = 0.0f32;
-        let mut total_damage = 0i32;
-        let mut total_gold = 0i32;
-        let mut total_cs = 0i32;
-        let mut wins = 0u32;
-        let mut long_games = 0u32;
-        let mut analyzed_matches = 0u32;
-
+        let mut games = Vec::new();
         for id in ids {
-            if let Some(m) = self.api.match_v5().get_match(route, &id).await? {
+            if let Some(m) = self.get_match_cached(&id, region).await? {
                 if let Some(p) = m.info.participants.iter().find(|p| p.puuid == puuid) {
-                    analyzed_matches += 1;
-                    total_kills += p.kills;
-                    total_deaths += p.deaths;
-                    total_assists += p.assists;
-                    total_vision += p.vision_score as f32;
-                    total_damage += p.total_damage_dealt_to_champions;
-                    total_gold += p.gold_earned;
-                    total_cs += p.total_minions_killed + p.neutral_minions_killed;
-                    
-                    if p.win { wins += 1; }
-                    if m.info.game_duration > 1800 { long_games += 1; } // 30+ minutes
+                    games.push(TraitGame {
+                        kills: p.kills,
+                        deaths: p.deaths,
+                        assists: p.assists,
+                        vision_score: p.vision_score,
+                        damage: p.total_damage_dealt_to_champions,
+                        gold: p.gold_earned,
+                        cs: p.total_minions_killed + p.neutral_minions_killed,
+                        win: p.win,
+                        game_duration: m.info.game_duration,
+                    });
                 }
             }
         }
 
-        let mut traits = Vec::new();
-
-        if analyzed_matches == 0 {
-            return Ok(traits);
-        }
-
-        let avg_kills = total_kills as f32 / analyzed_matches as f32;
-        let avg_deaths = total_deaths as f32 / analyzed_matches as f32;
-        let avg_assists = total_assists as f32 / analyzed_matches as f32;
-        let avg_vision = total_vision / analyzed_matches as f32;
-        let avg_damage = total_damage as f32 / analyzed_matches as f32;
-        let avg_gold = total_gold as f32 / analyzed_matches as f32;
-        let avg_cs = total_cs as f32 / analyzed_matches as f32;
-        let win_rate = wins as f32 / analyzed_matches as f32;
-
-        // Advanced trait detection
-        if avg_kills > 8.0 && avg_deaths < 6.0 {
-            traits.push("Aggressive Playmaker".to_string());
-        } else if avg_kills > 6.0 {
-            traits.push("High Impact Player".to_string());
-        }
-
-        if avg_deaths < 3.5 {
-            traits.push("Positioning Expert".to_string());
-        } else if avg_deaths > 7.0 {
-            traits.push("Risk Taker".to_string());
-        }
-
-        if avg_assists > 12.0 {
-            traits.push("Team Fight Specialist".to_string());
-        } else if avg_assists > 8.0 {
-            traits.push("Team Player".to_string());
-        }
-
-        if avg_vision > 35.0 {
-            traits.push("Vision Control Master".to_string());
-        } else if avg_vision < 15.0 {
-            traits.push("Needs Vision Improvement".to_string());
-        }
-
-        if avg_cs > 7.0 * 60.0 { // 7 CS per minute
-            traits.push("Farming Machine".to_string());
-        } else if avg_cs < 5.0 * 60.0 {
-            traits.push("Focus on CS".to_string());
-        }
-
-        if avg_damage > 25000.0 {
-            traits.push("Damage Dealer".to_string());
-        }
-
-        if avg_gold > 14000.0 {
-            traits.push("Gold Efficient".to_string());
-        }
-
-        if win_rate > 0.65 {
-            traits.push("Consistent Winner".to_string());
-        } else if win_rate < 0.45 {
-            traits.push("Needs Strategic Focus".to_string());
-        }
-
-        if long_games > analyzed_matches / 2 {
-            traits.push("Late Game Specialist".to_string());
-        }
-
-        // Behavioral analysis
-        let kda_ratio = (avg_kills + avg_assists) / avg_deaths.max(1.0);
-        if kda_ratio > 3.0 {
-            traits.push("Clutch Performer".to_string());
-        }
-
-        if total_kills > total_assists && avg_kills > 6.0 {
-            traits.push("Solo Carry Potential".to_string());
-        } else if total_assists > total_kills * 2 {
-            traits.push("Support Minded".to_string());
-        }
-
-        Ok(traits)
+        Ok(playstyle_traits(&games))
     }
 
     /// Robust match detection with multiple fallback strategies
@@ -943,24 +922,15 @@ impl RiotClient {
         let match_ids = self.get_match_history_ids(puuid, region, match_count).await?;
         let mut historical_matches = Vec::new();
 
+        let solo_tier = self.solo_tier(puuid, region).await;
+
         for match_id in match_ids {
-            match self.get_match_details(&match_id, region).await {
+            match self.get_match_cached(&match_id, region).await {
                 Ok(Some(match_details)) => {
-                    if let Some(participant) = match_details.info.participants.iter()
-                        .find(|p| p.puuid == puuid) {
-                        
-                        let historical_match = HistoricalMatchData {
-                            match_id: match_id.clone(),
-                            game_creation: match_details.info.game_creation,
-                            game_duration: match_details.info.game_duration,
-                            participant_data: self.convert_participant_to_data(participant),
-                            analytics_calculated: false, // Will be calculated later
-                            cached_timestamp: SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs() as i64,
-                        };
-                        
+                    if let Some(historical_match) = self.to_historical_match(&match_details, puuid) {
+                        if let Some(tier) = &solo_tier {
+                            self.store.tag_rank(&match_id, tier);
+                        }
                         historical_matches.push(historical_match);
                     }
                 }
@@ -978,6 +948,38 @@ impl RiotClient {
         Ok(historical_matches)
     }
 
+    /// Build the analytics record for one match from the player's perspective
+    pub fn to_historical_match(&self, match_details: &riven::models::match_v5::Match, puuid: &str) -> Option<HistoricalMatchData> {
+        let participant = match_details.info.participants.iter().find(|p| p.puuid == puuid)?;
+        let performance_ranking = score_match(match_details);
+        Some(HistoricalMatchData {
+            match_id: match_details.metadata.match_id.clone(),
+            game_creation: match_details.info.game_creation,
+            game_duration: match_details.info.game_duration,
+            queue_id: match_details.info.queue_id.0 as i32,
+            participant_data: self.convert_participant_to_data(participant),
+            participants: match_details.info.participants.iter().map(participant_ref).collect(),
+            team_context: compute_team_context(match_details, puuid, self.store.timeline(&match_details.metadata.match_id).as_ref()),
+            performance_score: performance_ranking.iter()
+                .find(|s| s.puuid == puuid)
+                .map(|s| s.score),
+            performance_ranking,
+            analytics_calculated: true,
+            cached_timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+        })
+    }
+
+    /// Analytics records for every stored match the player took part in, newest first
+    pub fn stored_history(&self, puuid: &str) -> Vec<HistoricalMatchData> {
+        self.store.matches_for(puuid)
+            .iter()
+            .filter_map(|m| self.to_historical_match(m, puuid))
+            .collect()
+    }
+
     /// Convert Riot API participant to our data structure
     fn convert_participant_to_data(&self, participant: &riven::models::match_v5::Participant) -> ParticipantData {
         ParticipantData {
@@ -1057,7 +1059,7 @@ impl RiotClient {
             summoner_level: participant.summoner_level,
             summoner_name: participant.summoner_name.clone(),
             team_early_surrendered: participant.team_early_surrendered,
-            team_id: format!("{:?}", participant.team_id).parse().unwrap_or(100),
+            team_id: team_id_of(participant),
             team_position: participant.team_position.clone(),
             time_c_cing_others: participant.time_c_cing_others,
             time_played: participant.time_played,
@@ -1093,15 +1095,92 @@ impl RiotClient {
         puuid: &str,
         region: &str,
         count: i32,
+    ) -> Result<Vec<String>, RiotApiError> {
+        self.get_match_ids_page(puuid, region, &MatchIdPage { start: 0, count, ..Default::default() }).await
+    }
+
+    /// Get one page of match IDs, newest first. `start_time`/`end_time` are epoch seconds.
+    pub async fn get_match_ids_page(
+        &self,
+        puuid: &str,
+        region: &str,
+        page: &MatchIdPage,
     ) -> Result<Vec<String>, RiotApiError> {
         let route = Self::parse_region(region).to_regional();
-        let path = format!("/lol/match/v5/matches/by-puuid/{}/ids?count={}", puuid, count);
+        let mut path = format!(
+            "/lol/match/v5/matches/by-puuid/{}/ids?start={}&count={}",
+            puuid, page.start, page.count
+        );
+        if let Some(start_time) = page.start_time {
+            path.push_str(&format!("&startTime={}", start_time));
+        }
+        if let Some(end_time) = page.end_time {
+            path.push_str(&format!("&endTime={}", end_time));
+        }
         let req = self.api.request(Method::GET, route.into(), &path);
         self.api
             .execute_val("match-v5.getMatchIdsByPUUID", route.into(), req)
             .await
     }
 
+    /// Current solo-queue tier, used as the rank bucket for matches this account ingests
+    pub async fn solo_tier(&self, puuid: &str, region: &str) -> Option<String> {
+        self.get_ranked_stats(puuid, region).await
+            .ok()
+            .and_then(|entries| {
+                entries.into_iter()
+                    .find(|e| e.queue_type == riven::consts::QueueType::RANKED_SOLO_5x5)
+                    .and_then(|e| e.tier.map(|t| t.to_string()))
+            })
+    }
+
+    /// Get a match from the local store, fetching and storing it on a miss
+    pub async fn get_match_cached(
+        &self,
+        match_id: &str,
+        region: &str,
+    ) -> Result<Option<riven::models::match_v5::Match>, RiotApiError> {
+        if let Some(stored) = self.store.get(match_id) {
+            return Ok(Some(stored));
+        }
+        let fetched = self.get_match_details(match_id, region).await?;
+        if let Some(m) = &fetched {
+            self.store.insert(m.clone());
+        }
+        Ok(fetched)
+    }
+
+    /// Get a match timeline from the local store, fetching it on a miss.
+    /// The match itself must already be stored for the timeline to be kept.
+    pub async fn get_timeline_cached(
+        &self,
+        match_id: &str,
+        region: &str,
+    ) -> Result<Option<MatchTimeline>, RiotApiError> {
+        if let Some(stored) = self.store.timeline(match_id) {
+            return Ok(Some(stored));
+        }
+        let fetched = self.get_match_timeline(match_id, region).await?;
+        if let Some(timeline) = &fetched {
+            self.store.insert_timeline(match_id, timeline.clone());
+        }
+        Ok(fetched)
+    }
+
+    /// Get the match-v5 timeline (events and per-minute participant frames)
+    async fn get_match_timeline(
+        &self,
+        match_id: &str,
+        region: &str,
+    ) -> Result<Option<MatchTimeline>, RiotApiError> {
+        let route = Self::parse_region(region).to_regional();
+        let path = format!("/lol/match/v5/matches/{}/timeline", match_id);
+        let req = self.api.request(Method::GET, route.into(), &path);
+        self.api
+            .execute_opt("match-v5.getTimeline", route.into(), req)
+            .await
+    }
+
     /// Get detailed match information
     async fn get_match_details(
         &self,
@@ -1116,3 +1195,127 @@ impl RiotClient {
             .await
     }
 }
+
+/// Per-game stats the playstyle trait heuristics read
+#[derive(Debug, Clone, Copy)]
+pub struct TraitGame {
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub vision_score: i32,
+    pub damage: i32,
+    pub gold: i32,
+    pub cs: i32,
+    pub win: bool,
+    pub game_duration: i64,
+}
+
+impl TraitGame {
+    pub fn from_historical(m: &HistoricalMatchData) -> Self {
+        let p = &m.participant_data;
+        TraitGame {
+            kills: p.kills,
+            deaths: p.deaths,
+            assists: p.assists,
+            vision_score: p.vision_score,
+            damage: p.total_damage_dealt_to_champions,
+            gold: p.gold_earned,
+            cs: p.total_minions_killed + p.neutral_minions_killed,
+            win: p.win,
+            game_duration: m.game_duration,
+        }
+    }
+}
+
+/// Playstyle labels from a player's averaged per-game stats
+pub fn playstyle_traits(games: &[TraitGame]) -> Vec<String> {
+    let analyzed_matches = games.len() as u32;
+    let total = |stat: fn(&TraitGame) -> i32| games.iter().map(stat).sum::<i32>();
+    let total_kills = total(|g| g.kills);
+    let total_deaths = total(|g| g.deaths);
+    let total_assists = total(|g| g.assists);
+    let total_vision = total(|g| g.vision_score) as f32;
+    let total_damage = total(|g| g.damage);
+    let total_gold = total(|g| g.gold);
+    let total_cs = total(|g| g.cs);
+    let wins = games.iter().filter(|g| g.win).count() as u32;
+    let long_games = games.iter().filter(|g| g.game_duration > 1800).count() as u32; // 30+ minutes
+
+    let mut traits = Vec::new();
+
+    if games.is_empty() {
+        return traits;
+    }
+
+    let avg_kills = total_kills as f32 / analyzed_matches as f32;
+    let avg_deaths = total_deaths as f32 / analyzed_matches as f32;
+    let avg_assists = total_assists as f32 / analyzed_matches as f32;
+    let avg_vision = total_vision / analyzed_matches as f32;
+    let avg_damage = total_damage as f32 / analyzed_matches as f32;
+    let avg_gold = total_gold as f32 / analyzed_matches as f32;
+    let avg_cs = total_cs as f32 / analyzed_matches as f32;
+    let win_rate = wins as f32 / analyzed_matches as f32;
+
+    // Advanced trait detection
+    if avg_kills > 8.0 && avg_deaths < 6.0 {
+        traits.push("Aggressive Playmaker".to_string());
+    } else if avg_kills > 6.0 {
+        traits.push("High Impact Player".to_string());
+    }
+
+    if avg_deaths < 3.5 {
+        traits.push("Positioning Expert".to_string());
+    } else if avg_deaths > 7.0 {
+        traits.push("Risk Taker".to_string());
+    }
+
+    if avg_assists > 12.0 {
+        traits.push("Team Fight Specialist".to_string());
+    } else if avg_assists > 8.0 {
+        traits.push("Team Player".to_string());
+    }
+
+    if avg_vision > 35.0 {
+        traits.push("Vision Control Master".to_string());
+    } else if avg_vision < 15.0 {
+        traits.push("Needs Vision Improvement".to_string());
+    }
+
+    if avg_cs > 7.0 * 60.0 { // 7 CS per minute
+        traits.push("Farming Machine".to_string());
+    } else if avg_cs < 5.0 * 60.0 {
+        traits.push("Focus on CS".to_string());
+    }
+
+    if avg_damage > 25000.0 {
+        traits.push("Damage Dealer".to_string());
+    }
+
+    if avg_gold > 14000.0 {
+        traits.push("Gold Efficient".to_string());
+    }
+
+    if win_rate > 0.65 {
+        traits.push("Consistent Winner".to_string());
+    } else if win_rate < 0.45 {
+        traits.push("Needs Strategic Focus".to_string());
+    }
+
+    if long_games > analyzed_matches / 2 {
+        traits.push("Late Game Specialist".to_string());
+    }
+
+    // Behavioral analysis
+    let kda_ratio = (avg_kills + avg_assists) / avg_deaths.max(1.0);
+    if kda_ratio > 3.0 {
+        traits.push("Clutch Performer".to_string());
+    }
+
+    if total_kills > total_assists && avg_kills > 6.0 {
+        traits.push("Solo Carry Potential".to_string());
+    } else if total_assists > total_kills * 2 {
+        traits.push("Support Minded".to_string());
+    }
+
+    traits
+}
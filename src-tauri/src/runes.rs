@@ -0,0 +1,204 @@
+use log::warn;
+use reqwest::Client;
+
+use crate::builds::BuildRunes;
+use crate::storage::{data_path, read_json, write_json};
+
+const RUNES_CACHE: &str = "static/runes.json";
+
+/// Stat shard options per row (offense, flex, defense); shards aren't in runesReforged.json
+const SHARD_ROWS: [[i32; 3]; 3] = [
+    [5008, 5005, 5007],
+    [5008, 5010, 5001],
+    [5011, 5013, 5001],
+];
+
+/// One rune tree from Data Dragon's runesReforged.json
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct RuneStyle {
+    pub id: i32,
+    pub key: String,
+    pub name: String,
+    pub slots: Vec<RuneSlot>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct RuneSlot {
+    pub runes: Vec<RuneInfo>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct RuneInfo {
+    pub id: i32,
+    pub key: String,
+    pub name: String,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct CachedRunes {
+    version: String,
+    styles: Vec<RuneStyle>,
+}
+
+/// A rune page as the client expects it: six perks followed by three shards
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RunePage {
+    pub name: String,
+    pub primary_style_id: i32,
+    pub sub_style_id: i32,
+    pub selected_perk_ids: Vec<i32>,
+    pub current: bool,
+}
+
+impl RunePage {
+    pub fn new(name: &str, primary_style: i32, sub_style: i32, perk_ids: &[i32], shard_ids: &[i32]) -> Self {
+        RunePage {
+            name: name.to_string(),
+            primary_style_id: primary_style,
+            sub_style_id: sub_style,
+            selected_perk_ids: perk_ids.iter().chain(shard_ids).copied().collect(),
+            current: true,
+        }
+    }
+
+    /// Page from a build returned by `get_champion_builds`
+    pub fn from_build(name: &str, runes: &BuildRunes) -> Self {
+        let perks: Vec<i32> = std::iter::once(runes.primary.keystone)
+            .chain(runes.primary.runes.iter().copied())
+            .chain(runes.secondary.runes.iter().copied())
+            .collect();
+        RunePage::new(name, runes.primary.tree, runes.secondary.tree, &perks, &runes.shards)
+    }
+}
+
+/// Rune static data for the patch, from the local cache when it's current
+pub async fn rune_data(version: Option<&str>) -> Result<Vec<RuneStyle>, String> {
+    let path = data_path(RUNES_CACHE);
+    let cached = path.as_deref().and_then(read_json::<CachedRunes>);
+
+    let version = match (version, &cached) {
+        (Some(version), _) => version.to_string(),
+        // Offline: any cached patch is better than nothing
+        (None, Some(cached)) => return Ok(cached.styles.clone()),
+        (None, None) => return Err("Rune data unavailable: no patch version and nothing cached".to_string()),
+    };
+    if let Some(cached) = cached.as_ref().filter(|c| c.version == version) {
+        return Ok(cached.styles.clone());
+    }
+
+    let url = format!("https://ddragon.leagueoflegends.com/cdn/{}/data/en_US/runesReforged.json", version);
+    let fetched: Result<Vec<RuneStyle>, reqwest::Error> = async {
+        Client::new().get(url).send().await?.error_for_status()?.json().await
+    }
+    .await;
+
+    match fetched {
+        Ok(styles) => {
+            if let Some(path) = &path {
+                if let Err(e) = write_json(path, &CachedRunes { version, styles: styles.clone() }) {
+                    warn!("Failed to cache rune data: {}", e);
+                }
+            }
+            Ok(styles)
+        }
+        Err(e) => match cached {
+            Some(cached) => {
+                warn!("Failed to fetch rune data for {}, using cached {}: {}", version, cached.version, e);
+                Ok(cached.styles)
+            }
+            None => Err(format!("Failed to fetch rune data: {}", e)),
+        },
+    }
+}
+
+fn slot_of(style: &RuneStyle, perk: i32) -> Option<usize> {
+    style.slots.iter().position(|slot| slot.runes.iter().any(|r| r.id == perk))
+}
+
+/// Check a page is one the client would accept: a keystone and one rune per row from the
+/// primary tree, two runes from different non-keystone rows of another tree, and one shard per row
+pub fn validate_page(page: &RunePage, styles: &[RuneStyle]) -> Result<(), String> {
+    let find = |id: i32| styles.iter().find(|s| s.id == id);
+    let primary = find(page.primary_style_id)
+        .ok_or_else(|| format!("Unknown primary style {}", page.primary_style_id))?;
+    let sub = find(page.sub_style_id)
+        .ok_or_else(|| format!("Unknown secondary style {}", page.sub_style_id))?;
+    if primary.id == sub.id {
+        return Err("Primary and secondary styles must differ".to_string());
+    }
+    if page.selected_perk_ids.len() != 9 {
+        return Err(format!("Expected 6 perks and 3 shards, got {} ids", page.selected_perk_ids.len()));
+    }
+    let (perks, shards) = page.selected_perk_ids.split_at(6);
+
+    for (row, perk) in perks[..4].iter().enumerate() {
+        if slot_of(primary, *perk) != Some(row) {
+            return Err(format!("Perk {} is not in row {} of {}", perk, row, primary.name));
+        }
+    }
+    let sub_rows: Vec<Option<usize>> = perks[4..].iter().map(|perk| slot_of(sub, *perk)).collect();
+    match sub_rows.as_slice() {
+        [Some(a), Some(b)] if *a > 0 && *b > 0 && a != b => {}
+        _ => return Err(format!("Secondary perks {:?} must come from two different rows of {}", &perks[4..], sub.name)),
+    }
+
+    for (row, shard) in shards.iter().enumerate() {
+        if !SHARD_ROWS[row].contains(shard) {
+            return Err(format!("Shard {} is not valid in row {}", shard, row));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style(id: i32, name: &str, rows: &[&[i32]]) -> RuneStyle {
+        RuneStyle {
+            id,
+            key: name.to_string(),
+            name: name.to_string(),
+            slots: rows.iter()
+                .map(|row| RuneSlot {
+                    runes: row.iter()
+                        .map(|&id| RuneInfo { id, key: id.to_string(), name: id.to_string() })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    fn styles() -> Vec<RuneStyle> {
+        vec![
+            style(8000, "Precision", &[&[8005, 8008, 8021, 8010], &[9101, 9111, 8009], &[9104, 9105, 9103], &[8014, 8017, 8299]]),
+            style(8100, "Domination", &[&[8112, 8124, 8128, 9923], &[8126, 8139, 8143], &[8136, 8120, 8138], &[8135, 8134, 8105, 8106]]),
+        ]
+    }
+
+    fn page(perks: &[i32], shards: &[i32]) -> RunePage {
+        RunePage::new("Test", 8000, 8100, perks, shards)
+    }
+
+    #[test]
+    fn accepts_a_legal_page() {
+        let page = page(&[8010, 9111, 9104, 8299, 8139, 8135], &[5008, 5008, 5011]);
+        assert_eq!(validate_page(&page, &styles()), Ok(()));
+    }
+
+    #[test]
+    fn rejects_misplaced_perks_and_shards() {
+        let styles = styles();
+        // Primary runes out of row order
+        assert!(validate_page(&page(&[9111, 8010, 9104, 8299, 8139, 8135], &[5008, 5008, 5011]), &styles).is_err());
+        // Both secondary runes from the same row
+        assert!(validate_page(&page(&[8010, 9111, 9104, 8299, 8139, 8126], &[5008, 5008, 5011]), &styles).is_err());
+        // Secondary keystone
+        assert!(validate_page(&page(&[8010, 9111, 9104, 8299, 8112, 8135], &[5008, 5008, 5011]), &styles).is_err());
+        // Defense shard in the offense row
+        assert!(validate_page(&page(&[8010, 9111, 9104, 8299, 8139, 8135], &[5011, 5008, 5011]), &styles).is_err());
+        // Missing shards
+        assert!(validate_page(&page(&[8010, 9111, 9104, 8299, 8139, 8135], &[]), &styles).is_err());
+    }
+}
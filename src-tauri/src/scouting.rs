@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use riven::models::champion_mastery_v4::ChampionMastery;
+use riven::models::league_v4::LeagueEntry;
+use riven::models::match_v5::Match;
+use riven::models::summoner_v4::Summoner;
+
+use crate::match_analysis::champion_id_of;
+
+/// Recent games fetched per live participant
+pub const SCOUTING_GAMES: usize = 10;
+
+/// Live participants scouted at once; each one is about a dozen rate-limited calls
+pub const SCOUTING_CONCURRENCY: usize = 3;
+
+/// Players seen again within this long (duo partners, the tracked player) aren't re-fetched
+const SOURCES_TTL: Duration = Duration::from_secs(30 * 60);
+
+static SOURCES_CACHE: LazyLock<Mutex<HashMap<String, (Instant, Arc<ScoutingSources>)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+const SMITE_SPELL_ID: i64 = 11;
+
+/// Stored games a champion needs before its most common position is trusted as its role
+const MIN_CHAMPION_ROLE_GAMES: u32 = 5;
+
+/// Recent games with a known position needed before a usual role is reported
+const MIN_ROLE_GAMES: usize = 3;
+
+/// Share of recent games in one position for it to count as the player's main role
+const MAIN_ROLE_SHARE: f32 = 0.6;
+
+/// Smurf signal score at which the flag is raised
+const SMURF_THRESHOLD: f32 = 0.5;
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoutingFlag {
+    /// "oneTrick", "comfortPick", "autofill" or "possibleSmurf"
+    pub kind: String,
+    /// 0-1
+    pub confidence: f32,
+    pub evidence: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerScouting {
+    pub puuid: String,
+    pub champion_id: i32,
+    pub likely_role: Option<LikelyRole>,
+    pub usual_role: Option<String>,
+    pub games_analyzed: u32,
+    pub flags: Vec<ScoutingFlag>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LikelyRole {
+    pub role: String,
+    /// "smite" or "championHistory"
+    pub source: String,
+}
+
+/// API data fetched for one live participant
+pub struct ScoutingSources {
+    pub summoner: Option<Summoner>,
+    pub ranked: Vec<LeagueEntry>,
+    pub masteries: Vec<ChampionMastery>,
+    /// Newest first
+    pub recent: Vec<Match>,
+}
+
+/// Sources fetched for `puuid` within the last `SOURCES_TTL`
+pub fn cached_sources(puuid: &str) -> Option<Arc<ScoutingSources>> {
+    let mut cache = SOURCES_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.retain(|_, (fetched, _)| fetched.elapsed() < SOURCES_TTL);
+    cache.get(puuid).map(|(_, sources)| sources.clone())
+}
+
+pub fn cache_sources(puuid: &str, sources: Arc<ScoutingSources>) {
+    SOURCES_CACHE.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(puuid.to_string(), (Instant::now(), sources));
+}
+
+/// Most common `team_position` for each champion across the stored corpus
+pub fn champion_roles<'a>(corpus: impl IntoIterator<Item = &'a Match>) -> HashMap<i32, String> {
+    let mut counts: HashMap<i32, HashMap<&str, u32>> = HashMap::new();
+    for m in corpus {
+        for p in &m.info.participants {
+            if !p.team_position.is_empty() {
+                *counts.entry(champion_id_of(p)).or_default().entry(p.team_position.as_str()).or_default() += 1;
+            }
+        }
+    }
+    counts.into_iter()
+        .filter_map(|(champion_id, positions)| {
+            positions.into_iter()
+                .filter(|(_, games)| *games >= MIN_CHAMPION_ROLE_GAMES)
+                .max_by_key(|(_, games)| *games)
+                .map(|(position, _)| (champion_id, position.to_string()))
+        })
+        .collect()
+}
+
+/// Smite means jungle; otherwise fall back to where the champion is usually played
+pub fn likely_role(
+    champion_id: i32,
+    spells: [i64; 2],
+    champion_roles: &HashMap<i32, String>,
+) -> Option<LikelyRole> {
+    if spells.contains(&SMITE_SPELL_ID) {
+        return Some(LikelyRole { role: "JUNGLE".to_string(), source: "smite".to_string() });
+    }
+    champion_roles.get(&champion_id)
+        .filter(|role| role.as_str() != "JUNGLE")
+        .map(|role| LikelyRole { role: role.clone(), source: "championHistory".to_string() })
+}
+
+fn one_trick_flag(puuid: &str, champion_id: i32, sources: &ScoutingSources) -> Option<ScoutingFlag> {
+    let total_points: i64 = sources.masteries.iter().map(|m| m.champion_points as i64).sum();
+    let mastery = sources.masteries.iter().find(|m| i16::from(m.champion_id) as i32 == champion_id);
+    let points = mastery.map(|m| m.champion_points as i64).unwrap_or(0);
+    let mastery_share = if total_points > 0 { points as f32 / total_points as f32 } else { 0.0 };
+
+    let games_with_player: Vec<i32> = sources.recent.iter()
+        .filter_map(|m| m.info.participants.iter().find(|p| p.puuid == puuid))
+        .map(champion_id_of)
+        .collect();
+    let on_champion = games_with_player.iter().filter(|id| **id == champion_id).count();
+    let recent_share = if games_with_player.is_empty() { 0.0 } else { on_champion as f32 / games_with_player.len() as f32 };
+
+    let mut evidence = Vec::new();
+    if let Some(m) = mastery {
+        evidence.push(format!("{} mastery points (level {})", m.champion_points, m.champion_level));
+    }
+    if total_points > 0 && points > 0 {
+        evidence.push(format!("{:.0}% of total mastery on this champion", mastery_share * 100.0));
+    }
+    if !games_with_player.is_empty() {
+        evidence.push(format!("{} of last {} games on this champion", on_champion, games_with_player.len()));
+    }
+
+    if recent_share >= 0.6 && (mastery_share >= 0.3 || points >= 200_000) {
+        Some(ScoutingFlag {
+            kind: "oneTrick".to_string(),
+            confidence: (recent_share * 0.6 + mastery_share.min(1.0) * 0.4).clamp(0.0, 1.0),
+            evidence,
+        })
+    } else if points >= 50_000 || on_champion >= 3 {
+        Some(ScoutingFlag {
+            kind: "comfortPick".to_string(),
+            confidence: ((points as f32 / 200_000.0).min(1.0) * 0.5 + recent_share * 0.5).clamp(0.0, 1.0),
+            evidence,
+        })
+    } else {
+        None
+    }
+}
+
+/// Most common recent position and its share of games with a known position
+fn usual_role(puuid: &str, recent: &[Match]) -> Option<(String, f32, usize)> {
+    let positions: Vec<&str> = recent.iter()
+        .filter_map(|m| m.info.participants.iter().find(|p| p.puuid == puuid))
+        .map(|p| p.team_position.as_str())
+        .filter(|position| !position.is_empty())
+        .collect();
+    if positions.len() < MIN_ROLE_GAMES {
+        return None;
+    }
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for position in &positions {
+        *counts.entry(*position).or_default() += 1;
+    }
+    counts.into_iter()
+        .max_by_key(|(_, games)| *games)
+        .map(|(position, games)| (position.to_string(), games as f32 / positions.len() as f32, positions.len()))
+}
+
+fn autofill_flag(
+    likely: Option<&LikelyRole>,
+    usual: Option<&(String, f32, usize)>,
+    puuid: &str,
+    recent: &[Match],
+) -> Option<ScoutingFlag> {
+    let (likely, (usual, share, games)) = (likely?, usual?);
+    if likely.role == *usual || *share < MAIN_ROLE_SHARE {
+        return None;
+    }
+    let in_likely_role = recent.iter()
+        .filter_map(|m| m.info.participants.iter().find(|p| p.puuid == puuid))
+        .filter(|p| p.team_position == likely.role)
+        .count();
+    let role_certainty = if likely.source == "smite" { 1.0 } else { 0.7 };
+    let off_role = 1.0 - in_likely_role as f32 / *games as f32;
+
+    Some(ScoutingFlag {
+        kind: "autofill".to_string(),
+        confidence: (share * off_role * role_certainty).clamp(0.0, 1.0),
+        evidence: vec![
+            format!("Played {} in {:.0}% of last {} games", usual, share * 100.0, games),
+            format!("Likely {} this game ({})", likely.role, if likely.source == "smite" { "has Smite" } else { "champion's usual role" }),
+            format!("{} recent games in {}", in_likely_role, likely.role),
+        ],
+    })
+}
+
+fn smurf_flag(puuid: &str, sources: &ScoutingSources) -> Option<ScoutingFlag> {
+    let mut score = 0.0f32;
+    let mut evidence = Vec::new();
+
+    if let Some(summoner) = &sources.summoner {
+        if summoner.summoner_level < 60 {
+            score += 0.3;
+            evidence.push(format!("Summoner level {}", summoner.summoner_level));
+        } else if summoner.summoner_level < 100 {
+            score += 0.15;
+            evidence.push(format!("Summoner level {}", summoner.summoner_level));
+        }
+    }
+
+    let entry = sources.ranked.iter()
+        .find(|e| e.queue_type == riven::consts::QueueType::RANKED_SOLO_5x5)
+        .or_else(|| sources.ranked.first());
+    if let Some(entry) = entry {
+        let played = entry.wins + entry.losses;
+        if played > 0 && played < 50 {
+            score += 0.2;
+            evidence.push(format!("Only {} ranked games this season", played));
+        }
+        if played >= 10 {
+            let win_rate = entry.wins as f32 / played as f32;
+            if win_rate >= 0.6 {
+                score += 0.25;
+                evidence.push(format!("{:.0}% ranked win rate over {} games", win_rate * 100.0, played));
+            }
+        }
+    }
+
+    let (kills, deaths, assists, games) = sources.recent.iter()
+        .filter_map(|m| m.info.participants.iter().find(|p| p.puuid == puuid))
+        .fold((0, 0, 0, 0), |(k, d, a, n), p| (k + p.kills, d + p.deaths, a + p.assists, n + 1));
+    if games >= MIN_ROLE_GAMES {
+        let kda = (kills + assists) as f32 / deaths.max(1) as f32;
+        if kda >= 4.0 {
+            score += 0.25;
+            evidence.push(format!("{:.1} KDA over last {} games", kda, games));
+        }
+    }
+
+    if score >= SMURF_THRESHOLD {
+        Some(ScoutingFlag {
+            kind: "possibleSmurf".to_string(),
+            confidence: score.min(1.0),
+            evidence,
+        })
+    } else {
+        None
+    }
+}
+
+/// One-trick, autofill and smurf signals for a live participant on `champion_id`
+pub fn scout_player(
+    puuid: &str,
+    champion_id: i32,
+    likely: Option<LikelyRole>,
+    sources: &ScoutingSources,
+) -> PlayerScouting {
+    let usual = usual_role(puuid, &sources.recent);
+    let flags: Vec<ScoutingFlag> = [
+        one_trick_flag(puuid, champion_id, sources),
+        autofill_flag(likely.as_ref(), usual.as_ref(), puuid, &sources.recent),
+        smurf_flag(puuid, sources),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    PlayerScouting {
+        puuid: puuid.to_string(),
+        champion_id,
+        likely_role: likely,
+        usual_role: usual.map(|(role, _, _)| role),
+        games_analyzed: sources.recent.iter()
+            .filter(|m| m.info.participants.iter().any(|p| p.puuid == puuid))
+            .count() as u32,
+        flags,
+    }
+}
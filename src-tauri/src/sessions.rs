@@ -0,0 +1,261 @@
+use crate::match_analysis::is_remake_duration;
+use crate::riot_client::{HistoricalMatchData, ParticipantRef};
+
+/// A gap longer than this between one game ending and the next starting begins a new session
+const SESSION_GAP_MS: i64 = 60 * 60_000;
+
+/// Loss streaks at or beyond this length share one bucket
+const MAX_TRACKED_STREAK: usize = 2;
+
+/// Games needed in a post-loss bucket before it can drive a tilt warning
+const MIN_TILT_SAMPLES: u32 = 5;
+
+/// Win-rate drop (percentage points) after a losing streak that counts as a tilt pattern
+const TILT_WIN_RATE_DROP: f32 = 8.0;
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaySession {
+    pub start: i64,
+    pub end: i64,
+    pub length_minutes: f32,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub win_rate: f32,
+    pub longest_win_streak: u32,
+    pub longest_loss_streak: u32,
+    pub kda: f32,
+    pub average_deaths: f32,
+    pub average_performance: Option<f32>,
+    /// Second-half minus first-half average performance score
+    pub performance_drift: Option<f32>,
+    pub match_ids: Vec<String>,
+}
+
+/// How the player performs in a game that follows N consecutive losses within a session
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PostLossPerformance {
+    pub preceding_losses: u32,
+    pub games: u32,
+    pub win_rate: f32,
+    pub kda: f32,
+    pub average_deaths: f32,
+    pub average_performance: Option<f32>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TiltAssessment {
+    pub tilted: bool,
+    pub current_loss_streak: u32,
+    pub session_games: u32,
+    pub baseline_win_rate: f32,
+    pub win_rate_after_streak: Option<f32>,
+    pub reason: String,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAnalysis {
+    pub sessions: Vec<PlaySession>,
+    pub post_loss: Vec<PostLossPerformance>,
+    pub current: Option<TiltAssessment>,
+}
+
+fn own_participant<'a>(m: &'a HistoricalMatchData, puuid: &str) -> Option<&'a ParticipantRef> {
+    m.participants.iter().find(|p| p.puuid == puuid)
+}
+
+/// The player's side of a game that counts towards records; remakes only affect session timing
+fn decided_game<'a>(m: &'a HistoricalMatchData, puuid: &str) -> Option<&'a ParticipantRef> {
+    if is_remake_duration(m.game_duration) {
+        return None;
+    }
+    own_participant(m, puuid)
+}
+
+fn kda_of(games: &[&ParticipantRef]) -> f32 {
+    let kills: i32 = games.iter().map(|p| p.kills).sum();
+    let deaths: i32 = games.iter().map(|p| p.deaths).sum();
+    let assists: i32 = games.iter().map(|p| p.assists).sum();
+    (kills + assists) as f32 / deaths.max(1) as f32
+}
+
+fn average(values: &[f32]) -> Option<f32> {
+    if values.is_empty() { None } else { Some(values.iter().sum::<f32>() / values.len() as f32) }
+}
+
+/// Group matches into sessions, oldest first
+pub fn group_sessions(matches: &[HistoricalMatchData]) -> Vec<Vec<&HistoricalMatchData>> {
+    let mut ordered: Vec<&HistoricalMatchData> = matches.iter().collect();
+    ordered.sort_by_key(|m| m.game_creation);
+
+    let mut sessions: Vec<Vec<&HistoricalMatchData>> = Vec::new();
+    for m in ordered {
+        let continues = sessions.last()
+            .and_then(|s| s.last())
+            .is_some_and(|prev| m.game_creation - (prev.game_creation + prev.game_duration * 1000) <= SESSION_GAP_MS);
+        match sessions.last_mut() {
+            Some(session) if continues => session.push(m),
+            _ => sessions.push(vec![m]),
+        }
+    }
+    sessions
+}
+
+fn summarize_session(games: &[&HistoricalMatchData], puuid: &str) -> Option<PlaySession> {
+    let first = games.first()?;
+    let last = games.last()?;
+    let players: Vec<&ParticipantRef> = games.iter().filter_map(|m| decided_game(m, puuid)).collect();
+    if players.is_empty() {
+        return None;
+    }
+
+    let (mut win_streak, mut loss_streak, mut longest_win, mut longest_loss) = (0, 0, 0, 0);
+    for p in &players {
+        if p.win {
+            win_streak += 1;
+            loss_streak = 0;
+        } else {
+            loss_streak += 1;
+            win_streak = 0;
+        }
+        longest_win = longest_win.max(win_streak);
+        longest_loss = longest_loss.max(loss_streak);
+    }
+
+    let scores: Vec<f32> = games.iter().filter_map(|m| m.performance_score).collect();
+    let half = scores.len() / 2;
+    let performance_drift = if half > 0 {
+        average(&scores[scores.len() - half..]).zip(average(&scores[..half])).map(|(late, early)| late - early)
+    } else {
+        None
+    };
+
+    let wins = players.iter().filter(|p| p.win).count() as u32;
+    let end = last.game_creation + last.game_duration * 1000;
+    Some(PlaySession {
+        start: first.game_creation,
+        end,
+        length_minutes: (end - first.game_creation) as f32 / 60_000.0,
+        games: players.len() as u32,
+        wins,
+        losses: players.len() as u32 - wins,
+        win_rate: wins as f32 / players.len() as f32 * 100.0,
+        longest_win_streak: longest_win,
+        longest_loss_streak: longest_loss,
+        kda: kda_of(&players),
+        average_deaths: players.iter().map(|p| p.deaths as f32).sum::<f32>() / players.len() as f32,
+        average_performance: average(&scores),
+        performance_drift,
+        match_ids: games.iter().map(|m| m.match_id.clone()).collect(),
+    })
+}
+
+/// Consecutive losses at the end of a run of games, passing over remakes
+fn trailing_losses(games: &[&HistoricalMatchData], puuid: &str) -> usize {
+    games.iter()
+        .rev()
+        .filter(|m| !is_remake_duration(m.game_duration))
+        .map_while(|m| own_participant(m, puuid).filter(|p| !p.win))
+        .count()
+}
+
+/// Performance of games by how many losses immediately preceded them in the same session
+fn post_loss_buckets(sessions: &[Vec<&HistoricalMatchData>], puuid: &str) -> Vec<PostLossPerformance> {
+    let mut buckets: Vec<(Vec<&ParticipantRef>, Vec<f32>)> = vec![(Vec::new(), Vec::new()); MAX_TRACKED_STREAK + 1];
+    for session in sessions {
+        for (index, m) in session.iter().enumerate() {
+            let p = match decided_game(m, puuid) {
+                Some(p) => p,
+                None => continue,
+            };
+            let streak = trailing_losses(&session[..index], puuid).min(MAX_TRACKED_STREAK);
+            buckets[streak].0.push(p);
+            if let Some(score) = m.performance_score {
+                buckets[streak].1.push(score);
+            }
+        }
+    }
+
+    buckets.into_iter()
+        .enumerate()
+        .map(|(preceding_losses, (players, scores))| {
+            let games = players.len() as u32;
+            let wins = players.iter().filter(|p| p.win).count() as f32;
+            PostLossPerformance {
+                preceding_losses: preceding_losses as u32,
+                games,
+                win_rate: if games > 0 { wins / games as f32 * 100.0 } else { 0.0 },
+                kda: kda_of(&players),
+                average_deaths: if games > 0 {
+                    players.iter().map(|p| p.deaths as f32).sum::<f32>() / games as f32
+                } else {
+                    0.0
+                },
+                average_performance: average(&scores),
+            }
+        })
+        .collect()
+}
+
+/// Compare the latest session's losing streak against the player's own history after similar streaks
+fn assess_current(sessions: &[Vec<&HistoricalMatchData>], post_loss: &[PostLossPerformance], puuid: &str, now_ms: i64) -> Option<TiltAssessment> {
+    let current = sessions.last()?;
+    let last = current.last()?;
+    if now_ms - (last.game_creation + last.game_duration * 1000) > SESSION_GAP_MS {
+        return None;
+    }
+
+    let streak = trailing_losses(current, puuid);
+    let baseline = &post_loss[0];
+    let bucket = &post_loss[streak.min(MAX_TRACKED_STREAK)];
+    let comparable = streak > 0 && bucket.games >= MIN_TILT_SAMPLES && baseline.games >= MIN_TILT_SAMPLES;
+    let drop = baseline.win_rate - bucket.win_rate;
+    let tilted = comparable && drop >= TILT_WIN_RATE_DROP;
+
+    let reason = if tilted {
+        format!(
+            "After {} straight loss{} you win {:.0}% of your next games, versus {:.0}% normally",
+            streak,
+            if streak == 1 { "" } else { "es" },
+            bucket.win_rate,
+            baseline.win_rate
+        )
+    } else if streak == 0 {
+        "Last game was a win".to_string()
+    } else if !comparable {
+        "Not enough history after losing streaks to compare".to_string()
+    } else {
+        format!("Your results after {} straight losses are in line with your baseline", streak)
+    };
+
+    Some(TiltAssessment {
+        tilted,
+        current_loss_streak: streak as u32,
+        session_games: current.len() as u32,
+        baseline_win_rate: baseline.win_rate,
+        win_rate_after_streak: if streak > 0 { Some(bucket.win_rate) } else { None },
+        reason,
+    })
+}
+
+/// Sessions (newest first), post-loss performance and the current session's tilt assessment
+pub fn analyze_sessions(matches: &[HistoricalMatchData], puuid: &str) -> SessionAnalysis {
+    let sessions = group_sessions(matches);
+    let post_loss = post_loss_buckets(&sessions, puuid);
+    let current = assess_current(&sessions, &post_loss, puuid, chrono::Utc::now().timestamp_millis());
+
+    let mut summaries: Vec<PlaySession> = sessions.iter()
+        .filter_map(|s| summarize_session(s, puuid))
+        .collect();
+    summaries.reverse();
+
+    SessionAnalysis {
+        sessions: summaries,
+        post_loss,
+        current,
+    }
+}
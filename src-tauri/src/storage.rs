@@ -0,0 +1,38 @@
+use once_cell::sync::OnceCell;
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Root directory for locally persisted data, set once during app setup
+pub static DATA_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// Resolve a path under the data directory, creating parent directories as needed
+pub fn data_path(relative: &str) -> Option<PathBuf> {
+    let path = DATA_DIR.get()?.join(relative);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).ok()?;
+    }
+    Some(path)
+}
+
+/// Resolve a directory under the data directory, creating it if missing
+pub fn data_subdir(relative: &str) -> Option<PathBuf> {
+    let path = DATA_DIR.get()?.join(relative);
+    std::fs::create_dir_all(&path).ok()?;
+    Some(path)
+}
+
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Write JSON via a temporary file so a crash never leaves a truncated file behind
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let contents = serde_json::to_string(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", path.display(), e))?;
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, contents)
+        .map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path)
+        .map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
@@ -0,0 +1,284 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use log::{info, warn};
+use tauri::{AppHandle, Emitter};
+
+use crate::riot_client::{MatchIdPage, RiotClient};
+use crate::storage::{data_path, read_json, write_json};
+
+/// match-v5 caps `count` at 100 per request
+const PAGE_SIZE: i32 = 100;
+
+/// Only one sync job runs at a time
+static SYNC_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Per-account sync state, persisted so an interrupted backfill resumes where it stopped
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyncCursor {
+    pub puuid: String,
+    /// `game_creation` (ms) of the newest match seen; incremental syncs start from here
+    pub newest_game_creation: Option<i64>,
+    /// Upper bound (epoch seconds) fixed when the backfill started, so new games
+    /// don't shift the page offsets between runs
+    pub backfill_end_time: Option<i64>,
+    /// Offset of the next backfill page
+    pub backfill_start: i32,
+    pub backfill_complete: bool,
+    /// Matches whose fetch failed (rate limits, server errors); retried on the next run
+    pub failed_match_ids: Vec<String>,
+    pub matches_synced: u32,
+    pub last_sync: Option<i64>,
+}
+
+impl SyncCursor {
+    fn note_failed(&mut self, match_id: &str, failed: bool) {
+        let known = self.failed_match_ids.iter().any(|id| id == match_id);
+        if failed && !known {
+            self.failed_match_ids.push(match_id.to_string());
+        } else if !failed && known {
+            self.failed_match_ids.retain(|id| id != match_id);
+        }
+    }
+
+    fn note_game(&mut self, game_creation: i64) {
+        self.newest_game_creation = Some(match self.newest_game_creation {
+            Some(newest) => newest.max(game_creation),
+            None => game_creation,
+        });
+    }
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncProgress {
+    pub puuid: String,
+    pub phase: String,
+    pub page: u32,
+    pub fetched: u32,
+    pub skipped: u32,
+    pub failed: u32,
+    pub done: bool,
+}
+
+fn cursor_path(puuid: &str) -> Option<std::path::PathBuf> {
+    data_path(&format!("sync/{}.json", puuid))
+}
+
+pub fn load_cursor(puuid: &str) -> SyncCursor {
+    cursor_path(puuid)
+        .and_then(|path| read_json::<SyncCursor>(&path))
+        .unwrap_or_else(|| SyncCursor { puuid: puuid.to_string(), ..Default::default() })
+}
+
+fn save_cursor(cursor: &SyncCursor) {
+    if let Some(path) = cursor_path(&cursor.puuid) {
+        if let Err(e) = write_json(&path, cursor) {
+            warn!("{}", e);
+        }
+    }
+}
+
+pub fn is_running() -> bool {
+    SYNC_RUNNING.load(Ordering::SeqCst)
+}
+
+/// Clears `SYNC_RUNNING` however the sync ends, including a panic or the future being dropped
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        SYNC_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Fetch and store every match in `ids` that isn't stored yet, updating the progress counters.
+/// Failed fetches are remembered on the cursor so the offsets can move past them safely.
+async fn ingest_page(
+    client: &RiotClient,
+    region: &str,
+    ids: &[String],
+    solo_tier: Option<&str>,
+    cursor: &mut SyncCursor,
+    progress: &mut SyncProgress,
+) {
+    for match_id in ids {
+        if let Some(m) = client.store().get(match_id) {
+            cursor.note_game(m.info.game_creation);
+            cursor.note_failed(match_id, false);
+            progress.skipped += 1;
+            continue;
+        }
+        match client.get_match_cached(match_id, region).await {
+            Ok(Some(m)) => {
+                if let Some(tier) = solo_tier {
+                    client.store().tag_rank(match_id, tier);
+                }
+                cursor.note_game(m.info.game_creation);
+                cursor.note_failed(match_id, false);
+                cursor.matches_synced += 1;
+                progress.fetched += 1;
+            }
+            Ok(None) => {
+                cursor.note_failed(match_id, false);
+                progress.skipped += 1;
+            }
+            Err(e) => {
+                warn!("Sync failed to fetch {}: {:?}", match_id, e);
+                cursor.note_failed(match_id, true);
+                progress.failed += 1;
+            }
+        }
+    }
+}
+
+/// Retry matches that failed to fetch on earlier runs
+async fn sync_failed(
+    app: &AppHandle,
+    client: &RiotClient,
+    region: &str,
+    solo_tier: Option<&str>,
+    cursor: &mut SyncCursor,
+) {
+    if cursor.failed_match_ids.is_empty() {
+        return;
+    }
+    let ids = cursor.failed_match_ids.clone();
+    let mut progress = SyncProgress {
+        puuid: cursor.puuid.clone(),
+        phase: "retry".to_string(),
+        page: 0,
+        fetched: 0,
+        skipped: 0,
+        failed: 0,
+        done: false,
+    };
+    info!("Retrying {} previously failed matches", ids.len());
+    ingest_page(client, region, &ids, solo_tier, cursor, &mut progress).await;
+    save_cursor(cursor);
+    progress.done = true;
+    let _ = app.emit("syncProgress", Some(progress));
+}
+
+/// Fetch games newer than the last known match, paging until a short page
+async fn sync_incremental(
+    app: &AppHandle,
+    client: &RiotClient,
+    region: &str,
+    solo_tier: Option<&str>,
+    cursor: &mut SyncCursor,
+) -> Result<(), String> {
+    let start_time = match cursor.newest_game_creation {
+        Some(created) => created / 1000,
+        None => return Ok(()),
+    };
+    let mut progress = SyncProgress {
+        puuid: cursor.puuid.clone(),
+        phase: "incremental".to_string(),
+        page: 0,
+        fetched: 0,
+        skipped: 0,
+        failed: 0,
+        done: false,
+    };
+
+    loop {
+        let page = MatchIdPage {
+            start: progress.page as i32 * PAGE_SIZE,
+            count: PAGE_SIZE,
+            start_time: Some(start_time),
+            end_time: None,
+        };
+        let ids = client.get_match_ids_page(&cursor.puuid, region, &page).await
+            .map_err(|e| format!("Failed to list new matches: {:?}", e))?;
+        ingest_page(client, region, &ids, solo_tier, cursor, &mut progress).await;
+        progress.page += 1;
+        save_cursor(cursor);
+        let _ = app.emit("syncProgress", Some(progress.clone()));
+        if (ids.len() as i32) < PAGE_SIZE {
+            break;
+        }
+    }
+
+    progress.done = true;
+    let _ = app.emit("syncProgress", Some(progress));
+    Ok(())
+}
+
+/// Page backwards through the whole history, resuming from the stored offset
+async fn sync_backfill(
+    app: &AppHandle,
+    client: &RiotClient,
+    region: &str,
+    solo_tier: Option<&str>,
+    cursor: &mut SyncCursor,
+) -> Result<(), String> {
+    if cursor.backfill_complete {
+        return Ok(());
+    }
+    let end_time = *cursor.backfill_end_time.get_or_insert_with(|| chrono::Utc::now().timestamp());
+    let mut progress = SyncProgress {
+        puuid: cursor.puuid.clone(),
+        phase: "backfill".to_string(),
+        page: (cursor.backfill_start / PAGE_SIZE) as u32,
+        fetched: 0,
+        skipped: 0,
+        failed: 0,
+        done: false,
+    };
+
+    loop {
+        let page = MatchIdPage {
+            start: cursor.backfill_start,
+            count: PAGE_SIZE,
+            start_time: None,
+            end_time: Some(end_time),
+        };
+        let ids = client.get_match_ids_page(&cursor.puuid, region, &page).await
+            .map_err(|e| format!("Failed to list match history: {:?}", e))?;
+        ingest_page(client, region, &ids, solo_tier, cursor, &mut progress).await;
+
+        cursor.backfill_start += ids.len() as i32;
+        cursor.backfill_complete = (ids.len() as i32) < PAGE_SIZE;
+        progress.page += 1;
+        save_cursor(cursor);
+        let _ = app.emit("syncProgress", Some(progress.clone()));
+        if cursor.backfill_complete {
+            break;
+        }
+    }
+
+    progress.done = true;
+    let _ = app.emit("syncProgress", Some(progress));
+    Ok(())
+}
+
+/// Run an incremental sync followed by any unfinished backfill for one account.
+/// Returns the updated cursor, or an error if a sync is already running.
+pub async fn run_sync(app: &AppHandle, client: &RiotClient, puuid: &str, region: &str) -> Result<SyncCursor, String> {
+    if SYNC_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A match sync is already running".to_string());
+    }
+    let _running = RunningGuard;
+
+    let mut cursor = load_cursor(puuid);
+    let solo_tier = client.solo_tier(puuid, region).await;
+    info!("Starting match sync for {} (backfill offset {})", puuid, cursor.backfill_start);
+
+    sync_failed(app, client, region, solo_tier.as_deref(), &mut cursor).await;
+    let mut result = sync_incremental(app, client, region, solo_tier.as_deref(), &mut cursor).await;
+    if result.is_ok() {
+        result = sync_backfill(app, client, region, solo_tier.as_deref(), &mut cursor).await;
+    }
+
+    cursor.last_sync = Some(chrono::Utc::now().timestamp());
+    save_cursor(&cursor);
+
+    let _ = app.emit("syncComplete", Some(serde_json::json!({
+        "puuid": puuid,
+        "matchesSynced": cursor.matches_synced,
+        "backfillComplete": cursor.backfill_complete,
+        "error": result.as_ref().err(),
+    })));
+    result.map(|_| cursor)
+}
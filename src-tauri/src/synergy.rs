@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use crate::match_analysis::is_remake_duration;
+use crate::riot_client::{HistoricalMatchData, ParticipantRef};
+
+/// Players need this many shared games to be reported as a partner or recurring opponent
+pub const MIN_SHARED_GAMES: u32 = 3;
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SynergyReport {
+    pub games_analyzed: u32,
+    pub partners: Vec<DuoPartner>,
+    pub recurring_opponents: Vec<RecurringOpponent>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DuoPartner {
+    pub puuid: String,
+    pub riot_id_name: String,
+    pub riot_id_tagline: String,
+    pub games_together: u32,
+    pub wins_together: u32,
+    pub win_rate_together: f32,
+    pub games_apart: u32,
+    pub win_rate_apart: f32,
+    pub win_rate_delta: f32,
+    pub kda_together: f32,
+    pub kda_apart: f32,
+    pub kda_delta: f32,
+    pub partner_kda_together: f32,
+    pub role_pairings: Vec<RolePairing>,
+    pub last_played: i64,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RolePairing {
+    pub player_role: String,
+    pub partner_role: String,
+    pub games: u32,
+    pub wins: u32,
+    pub win_rate: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringOpponent {
+    pub puuid: String,
+    pub riot_id_name: String,
+    pub riot_id_tagline: String,
+    pub games_against: u32,
+    pub wins_against: u32,
+    pub win_rate_against: f32,
+    pub champions_played: Vec<String>,
+    pub last_played: i64,
+}
+
+#[derive(Default, Clone, Copy)]
+struct KdaTotals {
+    games: u32,
+    wins: u32,
+    kills: i32,
+    deaths: i32,
+    assists: i32,
+}
+
+impl KdaTotals {
+    fn add(&mut self, p: &ParticipantRef) {
+        self.games += 1;
+        if p.win {
+            self.wins += 1;
+        }
+        self.kills += p.kills;
+        self.deaths += p.deaths;
+        self.assists += p.assists;
+    }
+
+    fn minus(&self, other: &KdaTotals) -> KdaTotals {
+        KdaTotals {
+            games: self.games - other.games,
+            wins: self.wins - other.wins,
+            kills: self.kills - other.kills,
+            deaths: self.deaths - other.deaths,
+            assists: self.assists - other.assists,
+        }
+    }
+
+    fn win_rate(&self) -> f32 {
+        if self.games > 0 { self.wins as f32 / self.games as f32 * 100.0 } else { 0.0 }
+    }
+
+    fn kda(&self) -> f32 {
+        (self.kills + self.assists) as f32 / self.deaths.max(1) as f32
+    }
+}
+
+struct PartnerAccumulator<'a> {
+    identity: &'a ParticipantRef,
+    player: KdaTotals,
+    partner: KdaTotals,
+    roles: HashMap<(String, String), (u32, u32)>,
+    last_played: i64,
+}
+
+struct OpponentAccumulator<'a> {
+    identity: &'a ParticipantRef,
+    games: u32,
+    wins: u32,
+    champions: Vec<String>,
+    last_played: i64,
+}
+
+/// Find repeat teammates and opponents across the player's history, ignoring remakes.
+/// `matches` should be newest first so the latest Riot ID is reported.
+pub fn analyze_synergy(matches: &[HistoricalMatchData], puuid: &str) -> SynergyReport {
+    let mut overall = KdaTotals::default();
+    let mut partners: HashMap<&str, PartnerAccumulator> = HashMap::new();
+    let mut opponents: HashMap<&str, OpponentAccumulator> = HashMap::new();
+
+    for m in matches.iter().filter(|m| !is_remake_duration(m.game_duration)) {
+        let me = match m.participants.iter().find(|p| p.puuid == puuid) {
+            Some(me) => me,
+            None => continue,
+        };
+        overall.add(me);
+
+        for other in m.participants.iter().filter(|p| p.puuid != puuid && !p.puuid.is_empty()) {
+            if other.team_id == me.team_id {
+                let acc = partners.entry(other.puuid.as_str()).or_insert_with(|| PartnerAccumulator {
+                    identity: other,
+                    player: KdaTotals::default(),
+                    partner: KdaTotals::default(),
+                    roles: HashMap::new(),
+                    last_played: m.game_creation,
+                });
+                acc.player.add(me);
+                acc.partner.add(other);
+                let pairing = acc.roles.entry((me.team_position.clone(), other.team_position.clone())).or_insert((0, 0));
+                pairing.0 += 1;
+                if me.win {
+                    pairing.1 += 1;
+                }
+            } else {
+                let acc = opponents.entry(other.puuid.as_str()).or_insert_with(|| OpponentAccumulator {
+                    identity: other,
+                    games: 0,
+                    wins: 0,
+                    champions: Vec::new(),
+                    last_played: m.game_creation,
+                });
+                acc.games += 1;
+                if me.win {
+                    acc.wins += 1;
+                }
+                if !acc.champions.contains(&other.champion_name) {
+                    acc.champions.push(other.champion_name.clone());
+                }
+            }
+        }
+    }
+
+    let mut duo_partners: Vec<DuoPartner> = partners.into_values()
+        .filter(|acc| acc.player.games >= MIN_SHARED_GAMES)
+        .map(|acc| {
+            let apart = overall.minus(&acc.player);
+            let mut role_pairings: Vec<RolePairing> = acc.roles.into_iter()
+                .map(|((player_role, partner_role), (games, wins))| RolePairing {
+                    player_role,
+                    partner_role,
+                    games,
+                    wins,
+                    win_rate: wins as f32 / games as f32 * 100.0,
+                })
+                .collect();
+            role_pairings.sort_by_key(|r| std::cmp::Reverse(r.games));
+
+            DuoPartner {
+                puuid: acc.identity.puuid.clone(),
+                riot_id_name: acc.identity.riot_id_name.clone(),
+                riot_id_tagline: acc.identity.riot_id_tagline.clone(),
+                games_together: acc.player.games,
+                wins_together: acc.player.wins,
+                win_rate_together: acc.player.win_rate(),
+                games_apart: apart.games,
+                win_rate_apart: apart.win_rate(),
+                win_rate_delta: acc.player.win_rate() - apart.win_rate(),
+                kda_together: acc.player.kda(),
+                kda_apart: apart.kda(),
+                kda_delta: acc.player.kda() - apart.kda(),
+                partner_kda_together: acc.partner.kda(),
+                role_pairings,
+                last_played: acc.last_played,
+            }
+        })
+        .collect();
+    duo_partners.sort_by_key(|p| (std::cmp::Reverse(p.games_together), std::cmp::Reverse(p.last_played)));
+
+    let mut recurring_opponents: Vec<RecurringOpponent> = opponents.into_values()
+        .filter(|acc| acc.games >= MIN_SHARED_GAMES)
+        .map(|acc| RecurringOpponent {
+            puuid: acc.identity.puuid.clone(),
+            riot_id_name: acc.identity.riot_id_name.clone(),
+            riot_id_tagline: acc.identity.riot_id_tagline.clone(),
+            games_against: acc.games,
+            wins_against: acc.wins,
+            win_rate_against: acc.wins as f32 / acc.games as f32 * 100.0,
+            champions_played: acc.champions,
+            last_played: acc.last_played,
+        })
+        .collect();
+    recurring_opponents.sort_by_key(|o| (std::cmp::Reverse(o.games_against), std::cmp::Reverse(o.last_played)));
+
+    SynergyReport {
+        games_analyzed: overall.games,
+        partners: duo_partners,
+        recurring_opponents,
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+/// Subset of the match-v5 timeline payload that the analytics modules read.
+/// Deserialized directly from the endpoint so unknown or missing fields are tolerated.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchTimeline {
+    pub info: TimelineInfo,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimelineInfo {
+    pub frame_interval: i64,
+    pub frames: Vec<TimelineFrame>,
+    pub participants: Vec<TimelineParticipant>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimelineParticipant {
+    pub participant_id: i32,
+    pub puuid: String,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimelineFrame {
+    pub timestamp: i64,
+    pub events: Vec<TimelineEvent>,
+    pub participant_frames: HashMap<String, ParticipantFrame>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimelineEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub timestamp: i64,
+    pub participant_id: Option<i32>,
+    pub item_id: Option<i32>,
+    pub before_id: Option<i32>,
+    pub after_id: Option<i32>,
+    pub gold_gain: Option<i32>,
+    pub skill_slot: Option<i32>,
+    pub level_up_type: Option<String>,
+    pub killer_id: Option<i32>,
+    pub victim_id: Option<i32>,
+    pub assisting_participant_ids: Option<Vec<i32>>,
+    pub position: Option<Position>,
+    pub ward_type: Option<String>,
+    pub creator_id: Option<i32>,
+    pub monster_type: Option<String>,
+    pub killer_team_id: Option<i32>,
+    pub team_id: Option<i32>,
+    pub building_type: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ParticipantFrame {
+    pub participant_id: i32,
+    pub total_gold: i32,
+    pub current_gold: i32,
+    pub xp: i32,
+    pub level: i32,
+    pub minions_killed: i32,
+    pub jungle_minions_killed: i32,
+    pub position: Option<Position>,
+}
+
+/// One purchase or sale in a reconstructed build order
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemEvent {
+    pub item_id: i32,
+    pub timestamp: i64,
+    pub sold: bool,
+}
+
+impl MatchTimeline {
+    /// Events of one type in chronological order
+    pub fn events_of_type<'a>(&'a self, event_type: &'a str) -> impl Iterator<Item = &'a TimelineEvent> + 'a {
+        self.info.frames.iter()
+            .flat_map(|f| f.events.iter())
+            .filter(move |e| e.event_type == event_type)
+    }
+
+    /// Participant frame closest to (but not after) the given minute
+    pub fn frame_at_minute(&self, participant_id: i32, minute: i64) -> Option<&ParticipantFrame> {
+        let target = minute * 60_000;
+        self.info.frames.iter()
+            .rev()
+            .find(|f| f.timestamp <= target)
+            .and_then(|f| f.participant_frames.get(&participant_id.to_string()))
+    }
+
+    /// Basic-ability skill sequence as letters, e.g. "QWEQQR..."
+    pub fn skill_sequence(&self, participant_id: i32) -> String {
+        self.events_of_type("SKILL_LEVEL_UP")
+            .filter(|e| e.participant_id == Some(participant_id))
+            .filter(|e| matches!(e.level_up_type.as_deref(), None | Some("NORMAL")))
+            .filter_map(|e| match e.skill_slot {
+                Some(1) => Some('Q'),
+                Some(2) => Some('W'),
+                Some(3) => Some('E'),
+                Some(4) => Some('R'),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Items bought before the first minute ends, with undone purchases removed
+    pub fn starting_items(&self, participant_id: i32) -> Vec<i32> {
+        let mut items: Vec<i32> = Vec::new();
+        for frame in &self.info.frames {
+            for event in &frame.events {
+                if event.participant_id != Some(participant_id) || event.timestamp > STARTING_ITEMS_WINDOW_MS {
+                    continue;
+                }
+                match (event.event_type.as_str(), event.item_id, event.before_id) {
+                    ("ITEM_PURCHASED", Some(item), _) => items.push(item),
+                    ("ITEM_UNDO", _, Some(before)) if before != 0 => {
+                        if let Some(index) = items.iter().rposition(|&i| i == before) {
+                            items.remove(index);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        items
+    }
+
+    /// Purchases and sales in order, with undone ones removed. An `ITEM_UNDO` carries the
+    /// undone purchase in `beforeId`, or the undone sale in `afterId`.
+    pub fn build_order(&self, participant_id: i32) -> Vec<ItemEvent> {
+        fn undo(order: &mut Vec<ItemEvent>, item: i32, sold: bool) {
+            if let Some(index) = order.iter().rposition(|e| e.item_id == item && e.sold == sold) {
+                order.remove(index);
+            }
+        }
+
+        let mut order: Vec<ItemEvent> = Vec::new();
+        for event in self.info.frames.iter().flat_map(|f| f.events.iter()) {
+            if event.participant_id != Some(participant_id) {
+                continue;
+            }
+            match (event.event_type.as_str(), event.item_id, event.before_id, event.after_id) {
+                ("ITEM_PURCHASED", Some(item), _, _) => order.push(ItemEvent { item_id: item, timestamp: event.timestamp, sold: false }),
+                ("ITEM_SOLD", Some(item), _, _) => order.push(ItemEvent { item_id: item, timestamp: event.timestamp, sold: true }),
+                ("ITEM_UNDO", _, Some(before), _) if before != 0 => undo(&mut order, before, false),
+                ("ITEM_UNDO", _, _, Some(after)) if after != 0 => undo(&mut order, after, true),
+                _ => {}
+            }
+        }
+        order
+    }
+}
+
+const STARTING_ITEMS_WINDOW_MS: i64 = 60_000;
+
+/// "Q>E>W" style max order from a skill sequence: abilities ordered by when
+/// they reach five ranks, with rank counts as a tie-breaker for short games
+pub fn max_order(sequence: &str) -> String {
+    let mut ranks: HashMap<char, u32> = HashMap::new();
+    let mut maxed_at: HashMap<char, usize> = HashMap::new();
+    for (index, skill) in sequence.chars().enumerate() {
+        if skill == 'R' {
+            continue;
+        }
+        let rank = ranks.entry(skill).or_insert(0);
+        *rank += 1;
+        if *rank == 5 {
+            maxed_at.insert(skill, index);
+        }
+    }
+
+    let mut skills = ['Q', 'W', 'E'];
+    skills.sort_by_key(|skill| (
+        maxed_at.get(skill).copied().unwrap_or(usize::MAX),
+        std::cmp::Reverse(ranks.get(skill).copied().unwrap_or(0)),
+    ));
+    skills.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(">")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item_event(event_type: &str, timestamp: i64, item_id: Option<i32>, before_id: Option<i32>, after_id: Option<i32>) -> TimelineEvent {
+        TimelineEvent {
+            event_type: event_type.to_string(),
+            timestamp,
+            participant_id: Some(1),
+            item_id,
+            before_id,
+            after_id,
+            ..Default::default()
+        }
+    }
+
+    fn timeline(events: Vec<TimelineEvent>) -> MatchTimeline {
+        MatchTimeline {
+            info: TimelineInfo {
+                frames: vec![TimelineFrame { events, ..Default::default() }],
+                ..Default::default()
+            },
+        }
+    }
+
+    fn order(timeline: &MatchTimeline) -> Vec<(i32, bool)> {
+        timeline.build_order(1).iter().map(|e| (e.item_id, e.sold)).collect()
+    }
+
+    #[test]
+    fn undone_purchase_is_dropped() {
+        let timeline = timeline(vec![
+            item_event("ITEM_PURCHASED", 1_000, Some(1055), None, None),
+            item_event("ITEM_PURCHASED", 2_000, Some(1036), None, None),
+            item_event("ITEM_UNDO", 3_000, None, Some(1036), Some(0)),
+        ]);
+        assert_eq!(order(&timeline), [(1055, false)]);
+    }
+
+    #[test]
+    fn undone_sale_is_dropped() {
+        let timeline = timeline(vec![
+            item_event("ITEM_PURCHASED", 1_000, Some(1055), None, None),
+            item_event("ITEM_SOLD", 2_000, Some(1055), None, None),
+            item_event("ITEM_UNDO", 3_000, None, Some(0), Some(1055)),
+        ]);
+        assert_eq!(order(&timeline), [(1055, false)]);
+    }
+
+    #[test]
+    fn other_participants_are_ignored() {
+        let mut theirs = item_event("ITEM_PURCHASED", 1_000, Some(1055), None, None);
+        theirs.participant_id = Some(2);
+        let timeline = timeline(vec![theirs, item_event("ITEM_PURCHASED", 2_000, Some(1056), None, None)]);
+        assert_eq!(order(&timeline), [(1056, false)]);
+    }
+}
@@ -0,0 +1,392 @@
+use riven::models::match_v5::{Match, Participant};
+
+use crate::match_analysis::{creep_score, player_score};
+
+/// Rolling window sizes, in games
+pub const TREND_WINDOWS: [usize; 3] = [10, 20, 50];
+
+/// Window used for a metric's headline label when enough games exist
+const HEADLINE_WINDOW: usize = 20;
+
+/// Fewest games a window needs before a slope is fitted
+const MIN_FIT_GAMES: usize = 5;
+
+/// Two-sided p-value below which a slope counts as a real trend
+const SIGNIFICANCE_LEVEL: f32 = 0.05;
+
+/// Games needed on each side of a patch boundary to test it for a change point
+const MIN_PATCH_GAMES: usize = 3;
+
+/// Standardized mean difference across a patch boundary that counts as a change point
+const CHANGE_POINT_EFFECT: f32 = 0.8;
+
+/// Games on a new patch used to judge how quickly the player adapts
+const ADAPTATION_GAMES: usize = 5;
+
+/// Per-game metric series tracked for trends. Deaths are inverted so that
+/// "improving" always means a rising series.
+const METRICS: [(&str, bool); 7] = [
+    ("kda", false),
+    ("csPerMinute", false),
+    ("goldPerMinute", false),
+    ("damagePerMinute", false),
+    ("visionPerMinute", false),
+    ("deathsPerMinute", true),
+    ("performanceScore", false),
+];
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendReport {
+    pub games_analyzed: u32,
+    pub metrics: Vec<MetricTrend>,
+    pub change_points: Vec<ChangePoint>,
+    pub adaptation_speed: Option<f32>,
+    pub scaling_effectiveness: Option<f32>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricTrend {
+    pub metric: String,
+    pub label: String,
+    pub current: f32,
+    pub windows: Vec<WindowTrend>,
+    /// Rolling 10-game mean, oldest first
+    pub rolling: Vec<f32>,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowTrend {
+    pub window: usize,
+    pub games: usize,
+    pub mean: f32,
+    pub previous_mean: Option<f32>,
+    /// Change per game across the window
+    pub slope: f32,
+    pub t_stat: f32,
+    pub p_value: f32,
+    pub label: String,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePoint {
+    pub metric: String,
+    pub patch: String,
+    pub previous_patch: String,
+    pub first_match_id: String,
+    pub before_mean: f32,
+    pub after_mean: f32,
+    pub effect_size: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LinearFit {
+    pub slope: f32,
+    pub intercept: f32,
+    pub t_stat: f32,
+    pub p_value: f32,
+}
+
+/// One game's metric values in `METRICS` order
+struct GameSample<'a> {
+    match_id: &'a str,
+    patch: String,
+    values: [f32; 7],
+}
+
+fn mean(values: &[f32]) -> f32 {
+    if values.is_empty() { 0.0 } else { values.iter().sum::<f32>() / values.len() as f32 }
+}
+
+fn std_dev(values: &[f32]) -> f32 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(values);
+    (values.iter().map(|v| (v - m).powi(2)).sum::<f32>() / (values.len() - 1) as f32).sqrt()
+}
+
+/// Natural log of the gamma function (Lanczos approximation, g = 7)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_81,
+        676.520_368_121_885,
+        -1_259.139_216_722_4,
+        771.323_428_777_653,
+        -176.615_029_162_141,
+        12.507_343_278_686_9,
+        -0.138_571_095_265_72,
+        9.984_369_578_019_57e-6,
+        1.505_632_735_149_31e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..].iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// Continued fraction for the incomplete beta function (modified Lentz's method)
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-30;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..200 {
+        let m = m as f64;
+        let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        let odd = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        for coefficient in [even, odd] {
+            d = 1.0 + coefficient * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + coefficient / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            h *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+    h
+}
+
+/// Regularized incomplete beta function I_x(a, b)
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Two-sided p-value of a Student's t statistic with `df` degrees of freedom
+fn t_p_value(t_stat: f32, df: usize) -> f32 {
+    let df = df as f64;
+    let t = t_stat as f64;
+    incomplete_beta(df / 2.0, 0.5, df / (df + t * t)) as f32
+}
+
+/// Ordinary least squares fit of `ys` against `xs`, with a Student's t p-value for the slope
+pub fn linear_fit(xs: &[f32], ys: &[f32]) -> Option<LinearFit> {
+    let n = xs.len().min(ys.len());
+    if n < 3 {
+        return None;
+    }
+    let (mean_x, mean_y) = (mean(&xs[..n]), mean(&ys[..n]));
+    let sxx: f32 = xs[..n].iter().map(|x| (x - mean_x).powi(2)).sum();
+    if sxx == 0.0 {
+        return None;
+    }
+    let sxy: f32 = xs[..n].iter().zip(&ys[..n]).map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope * mean_x;
+
+    let residual_ss: f32 = xs[..n].iter()
+        .zip(&ys[..n])
+        .map(|(x, y)| (y - (intercept + slope * x)).powi(2))
+        .sum();
+    let std_error = (residual_ss / (n - 2) as f32 / sxx).sqrt();
+    let t_stat = if std_error > 0.0 { slope / std_error } else { 0.0 };
+
+    Some(LinearFit {
+        slope,
+        intercept,
+        t_stat,
+        p_value: t_p_value(t_stat, n - 2),
+    })
+}
+
+fn trend_label(fit: Option<&LinearFit>) -> &'static str {
+    match fit {
+        Some(fit) if fit.p_value < SIGNIFICANCE_LEVEL && fit.slope > 0.0 => "improving",
+        Some(fit) if fit.p_value < SIGNIFICANCE_LEVEL && fit.slope < 0.0 => "declining",
+        _ => "stable",
+    }
+}
+
+/// "14.3.567.1234" -> "14.3"
+fn patch_of(game_version: &str) -> String {
+    game_version.split('.').take(2).collect::<Vec<_>>().join(".")
+}
+
+/// `None` for remakes, which have no composite score and distorted per-minute stats
+fn sample_game<'a>(m: &'a Match, p: &Participant, puuid: &str) -> Option<GameSample<'a>> {
+    let score = player_score(m, puuid)?;
+    let minutes = (m.info.game_duration as f32 / 60.0).max(1.0);
+    Some(GameSample {
+        match_id: &m.metadata.match_id,
+        patch: patch_of(&m.info.game_version),
+        values: [
+            (p.kills + p.assists) as f32 / p.deaths.max(1) as f32,
+            creep_score(p) as f32 / minutes,
+            p.gold_earned as f32 / minutes,
+            p.total_damage_dealt_to_champions as f32 / minutes,
+            p.vision_score as f32 / minutes,
+            p.deaths as f32 / minutes,
+            score,
+        ],
+    })
+}
+
+fn window_trend(series: &[f32], window: usize, inverted: bool) -> Option<WindowTrend> {
+    // Only the smallest window is reported partly filled; a short history would
+    // otherwise repeat it under every larger window
+    if series.len() < MIN_FIT_GAMES || (series.len() < window && window > TREND_WINDOWS[0]) {
+        return None;
+    }
+    let start = series.len().saturating_sub(window);
+    let recent = &series[start..];
+    let previous = &series[start.saturating_sub(window)..start];
+
+    let xs: Vec<f32> = (0..recent.len()).map(|i| i as f32).collect();
+    let ys: Vec<f32> = recent.iter().map(|v| if inverted { -v } else { *v }).collect();
+    let fit = linear_fit(&xs, &ys);
+
+    Some(WindowTrend {
+        window,
+        games: recent.len(),
+        mean: mean(recent),
+        previous_mean: if previous.is_empty() { None } else { Some(mean(previous)) },
+        slope: fit.map(|f| if inverted { -f.slope } else { f.slope }).unwrap_or(0.0),
+        t_stat: fit.map(|f| f.t_stat).unwrap_or(0.0),
+        p_value: fit.map(|f| f.p_value).unwrap_or(1.0),
+        label: trend_label(fit.as_ref()).to_string(),
+    })
+}
+
+/// Patch boundaries where a metric's mean shifts by a large standardized amount
+fn change_points(samples: &[GameSample], metric_index: usize, metric: &str) -> Vec<ChangePoint> {
+    let mut patches: Vec<(&str, &str, Vec<f32>)> = Vec::new();
+    for s in samples {
+        match patches.last_mut() {
+            Some((patch, _, values)) if *patch == s.patch => values.push(s.values[metric_index]),
+            _ => patches.push((s.patch.as_str(), s.match_id, vec![s.values[metric_index]])),
+        }
+    }
+
+    patches.windows(2)
+        .filter(|pair| pair[0].2.len() >= MIN_PATCH_GAMES && pair[1].2.len() >= MIN_PATCH_GAMES)
+        .filter_map(|pair| {
+            let (before, after) = (&pair[0].2, &pair[1].2);
+            let pooled = ((std_dev(before).powi(2) + std_dev(after).powi(2)) / 2.0).sqrt();
+            if pooled == 0.0 {
+                return None;
+            }
+            let effect_size = (mean(after) - mean(before)) / pooled;
+            if effect_size.abs() < CHANGE_POINT_EFFECT {
+                return None;
+            }
+            Some(ChangePoint {
+                metric: metric.to_string(),
+                patch: pair[1].0.to_string(),
+                previous_patch: pair[0].0.to_string(),
+                first_match_id: pair[1].1.to_string(),
+                before_mean: mean(before),
+                after_mean: mean(after),
+                effect_size,
+            })
+        })
+        .collect()
+}
+
+/// 0-100 rating of performance in the first games of each new patch relative to
+/// the player's overall average; 50 means no dip
+fn adaptation_speed(samples: &[GameSample]) -> Option<f32> {
+    let score_index = METRICS.len() - 1;
+    let overall = mean(&samples.iter().map(|s| s.values[score_index]).collect::<Vec<_>>());
+    let mut deltas = Vec::new();
+    for (index, pair) in samples.windows(2).enumerate() {
+        if pair[0].patch == pair[1].patch {
+            continue;
+        }
+        let first_games: Vec<f32> = samples[index + 1..].iter()
+            .take_while(|s| s.patch == pair[1].patch)
+            .take(ADAPTATION_GAMES)
+            .map(|s| s.values[score_index])
+            .collect();
+        if first_games.len() >= MIN_PATCH_GAMES {
+            deltas.push(mean(&first_games) - overall);
+        }
+    }
+    if deltas.is_empty() {
+        None
+    } else {
+        Some((50.0 + mean(&deltas) * 20.0).clamp(0.0, 100.0))
+    }
+}
+
+/// 0-100 rating from how the composite score changes with game length; 50 means flat
+pub fn scaling_effectiveness(matches: &[Match], puuid: &str) -> Option<f32> {
+    let (minutes, scores): (Vec<f32>, Vec<f32>) = matches.iter()
+        .filter_map(|m| player_score(m, puuid).map(|score| (m.info.game_duration as f32 / 60.0, score)))
+        .unzip();
+    // Score change per 10 minutes of game time, scaled so +/-2.5 points spans the range
+    linear_fit(&minutes, &scores).map(|fit| (50.0 + fit.slope * 10.0 * 20.0).clamp(0.0, 100.0))
+}
+
+/// Rolling-window trends for the player's key per-minute metrics. `matches` may be in any order.
+pub fn analyze_trends(matches: &[Match], puuid: &str) -> TrendReport {
+    let mut ordered: Vec<&Match> = matches.iter().collect();
+    ordered.sort_by_key(|m| m.info.game_creation);
+    let samples: Vec<GameSample> = ordered.iter()
+        .filter_map(|m| {
+            m.info.participants.iter()
+                .find(|p| p.puuid == puuid)
+                .and_then(|p| sample_game(m, p, puuid))
+        })
+        .collect();
+
+    let mut metrics = Vec::new();
+    let mut all_change_points = Vec::new();
+    for (index, (metric, inverted)) in METRICS.iter().enumerate() {
+        let series: Vec<f32> = samples.iter().map(|s| s.values[index]).collect();
+        let windows: Vec<WindowTrend> = TREND_WINDOWS.iter()
+            .filter_map(|&window| window_trend(&series, window, *inverted))
+            .collect();
+        let headline = windows.iter()
+            .find(|w| w.window == HEADLINE_WINDOW)
+            .or_else(|| windows.first());
+
+        metrics.push(MetricTrend {
+            metric: metric.to_string(),
+            label: headline.map(|w| w.label.clone()).unwrap_or_else(|| "stable".to_string()),
+            current: windows.first().map(|w| w.mean).unwrap_or(0.0),
+            rolling: series.windows(TREND_WINDOWS[0]).map(mean).collect(),
+            windows,
+        });
+        all_change_points.extend(change_points(&samples, index, metric));
+    }
+
+    TrendReport {
+        games_analyzed: samples.len() as u32,
+        metrics,
+        change_points: all_change_points,
+        adaptation_speed: adaptation_speed(&samples),
+        scaling_effectiveness: scaling_effectiveness(matches, puuid),
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+
+use riven::models::match_v5::Match;
+
+use crate::heatmaps::phase_of;
+use crate::match_analysis::role_benchmark;
+use crate::timeline::MatchTimeline;
+
+/// `WARD_KILL` events don't say whose ward died, so a control ward that isn't replaced
+/// is assumed to be cleared after this long
+const CONTROL_WARD_EXPECTED_LIFETIME_MS: i64 = 4 * 60_000;
+
+/// Share of the game with a control ward up that counts as full marks
+const TARGET_CONTROL_UPTIME: f32 = 0.5;
+
+/// Rating weights: wards placed, wards cleared, control ward uptime, vision score
+const RATING_WEIGHTS: [f32; 4] = [0.3, 0.25, 0.2, 0.25];
+
+#[derive(Debug, serde::Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseVision {
+    pub phase: String,
+    pub wards_placed: u32,
+    pub wards_cleared: u32,
+    pub control_wards_placed: u32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WardTypeCount {
+    pub ward_type: String,
+    pub count: u32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MinuteVision {
+    pub minute: u32,
+    pub wards_placed: u32,
+    pub wards_cleared: u32,
+    pub control_ward_active: bool,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameVision {
+    pub match_id: String,
+    pub role: String,
+    pub duration_minutes: f32,
+    pub vision_score: i32,
+    pub wards_placed: u32,
+    pub wards_cleared: u32,
+    pub control_wards_placed: u32,
+    /// Approximate share of the game (0–1) with one of the player's control wards up
+    pub control_ward_uptime: f32,
+    pub phases: Vec<PhaseVision>,
+    pub ward_types: Vec<WardTypeCount>,
+    pub per_minute: Vec<MinuteVision>,
+    /// 0–100 against the role's expectations
+    pub rating: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VisionReport {
+    pub games_analyzed: u32,
+    pub wards_placed_per_min: f32,
+    pub wards_cleared_per_min: f32,
+    pub control_wards_per_game: f32,
+    pub control_ward_uptime: f32,
+    pub vision_score_per_min: f32,
+    pub phases: Vec<PhaseVision>,
+    pub ward_types: Vec<WardTypeCount>,
+    pub vision_control_rating: f32,
+    pub games: Vec<GameVision>,
+}
+
+/// Wards placed and cleared per minute that count as full marks for a role
+fn ward_targets(team_position: &str) -> (f32, f32) {
+    match team_position {
+        "UTILITY" => (1.0, 0.25),
+        "JUNGLE" => (0.5, 0.2),
+        _ => (0.35, 0.1),
+    }
+}
+
+fn ratio(value: f32, target: f32) -> f32 {
+    if target <= 0.0 {
+        return 0.0;
+    }
+    (value / target).min(1.0)
+}
+
+fn phase_entry<'a>(phases: &'a mut Vec<PhaseVision>, timestamp: i64) -> &'a mut PhaseVision {
+    let phase = phase_of(timestamp);
+    match phases.iter().position(|p| p.phase == phase) {
+        Some(index) => &mut phases[index],
+        None => {
+            phases.push(PhaseVision { phase: phase.to_string(), ..Default::default() });
+            phases.last_mut().unwrap()
+        }
+    }
+}
+
+fn sort_phases(phases: &mut [PhaseVision]) {
+    phases.sort_by_key(|p| ["early", "mid", "late"].iter().position(|name| *name == p.phase));
+}
+
+/// Time each control ward stays up: until the player places the next one (only one can be
+/// out at a time), the expected lifetime runs out, or the game ends
+fn control_ward_intervals(placed_at: &[i64], game_end: i64) -> Vec<(i64, i64)> {
+    placed_at.iter()
+        .enumerate()
+        .map(|(index, &start)| {
+            let mut end = (start + CONTROL_WARD_EXPECTED_LIFETIME_MS).min(game_end);
+            if let Some(&next) = placed_at.get(index + 1) {
+                end = end.min(next);
+            }
+            (start, end.max(start))
+        })
+        .collect()
+}
+
+/// Ward placement and clearing for the player in one game, from `WARD_PLACED` and `WARD_KILL`
+/// events, bucketed by game minute. The game ends at the later of the match duration and the
+/// last timeline frame
+pub fn analyze_game_vision(m: &Match, timeline: &MatchTimeline, puuid: &str) -> Option<GameVision> {
+    let me = m.info.participants.iter().find(|p| p.puuid == puuid)?;
+    let participant_id = me.participant_id;
+    let game_end = (m.info.game_duration * 1000)
+        .max(timeline.info.frames.last().map(|f| f.timestamp).unwrap_or(0));
+    if game_end <= 0 {
+        return None;
+    }
+    let minutes = (game_end / 60_000 + 1) as usize;
+
+    let mut phases: Vec<PhaseVision> = Vec::new();
+    let mut ward_types: HashMap<String, u32> = HashMap::new();
+    let mut per_minute: Vec<MinuteVision> = (0..minutes)
+        .map(|minute| MinuteVision {
+            minute: minute as u32,
+            wards_placed: 0,
+            wards_cleared: 0,
+            control_ward_active: false,
+        })
+        .collect();
+    let mut control_placed_at: Vec<i64> = Vec::new();
+    let (mut wards_placed, mut wards_cleared) = (0u32, 0u32);
+
+    for frame in &timeline.info.frames {
+        for event in &frame.events {
+            let minute = ((event.timestamp / 60_000) as usize).min(minutes - 1);
+            let ward_type = event.ward_type.as_deref().unwrap_or("UNDEFINED");
+            match event.event_type.as_str() {
+                // Summoned objects (Teemo shrooms and the like) are reported as UNDEFINED
+                "WARD_PLACED" if event.creator_id == Some(participant_id) && ward_type != "UNDEFINED" => {
+                    wards_placed += 1;
+                    per_minute[minute].wards_placed += 1;
+                    *ward_types.entry(ward_type.to_string()).or_default() += 1;
+                    let phase = phase_entry(&mut phases, event.timestamp);
+                    phase.wards_placed += 1;
+                    if ward_type == "CONTROL_WARD" {
+                        phase.control_wards_placed += 1;
+                        control_placed_at.push(event.timestamp);
+                    }
+                }
+                "WARD_KILL" if event.killer_id == Some(participant_id) && ward_type != "UNDEFINED" => {
+                    wards_cleared += 1;
+                    per_minute[minute].wards_cleared += 1;
+                    phase_entry(&mut phases, event.timestamp).wards_cleared += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let intervals = control_ward_intervals(&control_placed_at, game_end);
+    let uptime_ms: i64 = intervals.iter().map(|(start, end)| end - start).sum();
+    for entry in per_minute.iter_mut() {
+        let midpoint = entry.minute as i64 * 60_000 + 30_000;
+        entry.control_ward_active = intervals.iter().any(|(start, end)| *start <= midpoint && midpoint < *end);
+    }
+
+    let duration_minutes = game_end as f32 / 60_000.0;
+    let control_ward_uptime = uptime_ms as f32 / game_end as f32;
+    let (placed_target, cleared_target) = ward_targets(&me.team_position);
+    let components = [
+        ratio(wards_placed as f32 / duration_minutes, placed_target),
+        ratio(wards_cleared as f32 / duration_minutes, cleared_target),
+        ratio(control_ward_uptime, TARGET_CONTROL_UPTIME),
+        ratio(me.vision_score as f32 / duration_minutes, role_benchmark(&me.team_position).vision_per_min),
+    ];
+    let rating = components.iter().zip(RATING_WEIGHTS).map(|(c, w)| c * w).sum::<f32>() * 100.0;
+
+    let mut ward_types: Vec<WardTypeCount> = ward_types.into_iter()
+        .map(|(ward_type, count)| WardTypeCount { ward_type, count })
+        .collect();
+    ward_types.sort_by_key(|w| std::cmp::Reverse(w.count));
+    sort_phases(&mut phases);
+
+    Some(GameVision {
+        match_id: m.metadata.match_id.clone(),
+        role: if me.team_position.is_empty() { "UNKNOWN".to_string() } else { me.team_position.clone() },
+        duration_minutes,
+        vision_score: me.vision_score,
+        wards_placed,
+        wards_cleared,
+        control_wards_placed: control_placed_at.len() as u32,
+        control_ward_uptime,
+        phases,
+        ward_types,
+        per_minute,
+        rating,
+    })
+}
+
+/// Average vision rating across games, `None` when no timelines were available
+pub fn vision_control_rating(games: &[GameVision]) -> Option<f32> {
+    if games.is_empty() {
+        return None;
+    }
+    Some(games.iter().map(|g| g.rating).sum::<f32>() / games.len() as f32)
+}
+
+/// Vision habits across the player's games that have timelines
+pub fn vision_report(games: &[(Match, MatchTimeline)], puuid: &str, role: Option<&str>) -> VisionReport {
+    let analyzed: Vec<GameVision> = games.iter()
+        .filter_map(|(m, timeline)| analyze_game_vision(m, timeline, puuid))
+        .filter(|g| match role {
+            Some(role) => g.role == role,
+            None => true,
+        })
+        .collect();
+
+    let total_minutes: f32 = analyzed.iter().map(|g| g.duration_minutes).sum();
+    let per_min = |f: fn(&GameVision) -> f32| -> f32 {
+        if total_minutes > 0.0 { analyzed.iter().map(f).sum::<f32>() / total_minutes } else { 0.0 }
+    };
+    let per_game = |f: fn(&GameVision) -> f32| -> f32 {
+        if analyzed.is_empty() { 0.0 } else { analyzed.iter().map(f).sum::<f32>() / analyzed.len() as f32 }
+    };
+
+    let mut phases: Vec<PhaseVision> = Vec::new();
+    let mut ward_types: Vec<WardTypeCount> = Vec::new();
+    for game in &analyzed {
+        for phase in &game.phases {
+            match phases.iter_mut().find(|p| p.phase == phase.phase) {
+                Some(total) => {
+                    total.wards_placed += phase.wards_placed;
+                    total.wards_cleared += phase.wards_cleared;
+                    total.control_wards_placed += phase.control_wards_placed;
+                }
+                None => phases.push(phase.clone()),
+            }
+        }
+        for ward in &game.ward_types {
+            match ward_types.iter_mut().find(|w| w.ward_type == ward.ward_type) {
+                Some(total) => total.count += ward.count,
+                None => ward_types.push(ward.clone()),
+            }
+        }
+    }
+    sort_phases(&mut phases);
+    ward_types.sort_by_key(|w| std::cmp::Reverse(w.count));
+
+    VisionReport {
+        games_analyzed: analyzed.len() as u32,
+        wards_placed_per_min: per_min(|g| g.wards_placed as f32),
+        wards_cleared_per_min: per_min(|g| g.wards_cleared as f32),
+        control_wards_per_game: per_game(|g| g.control_wards_placed as f32),
+        control_ward_uptime: per_game(|g| g.control_ward_uptime),
+        vision_score_per_min: per_min(|g| g.vision_score as f32),
+        phases,
+        ward_types,
+        vision_control_rating: vision_control_rating(&analyzed).unwrap_or(0.0),
+        games: analyzed,
+    }
+}
@@ -0,0 +1,185 @@
+use riven::models::champion_mastery_v4::ChampionMastery;
+use riven::models::league_v4::LeagueEntry;
+use riven::models::match_v5::Match;
+
+use crate::match_analysis::{champion_id_of, is_remake};
+use crate::match_store::tier_index;
+use crate::premades::PremadeGroup;
+
+/// Logit per 100 points of average MMR-proxy difference
+const RANK_WEIGHT: f32 = 0.12;
+
+/// Logit per unit difference in average champion strength (win rate plus mastery bonus)
+const CHAMPION_WEIGHT: f32 = 1.5;
+
+/// Mastery points at which the champion experience bonus is maxed out
+const MASTERY_CAP: f32 = 300_000.0;
+
+/// Champion strength added at full mastery; recent games alone are too few to move the
+/// shrunk win rate, so long-term experience carries most of this feature
+const MASTERY_BONUS: f32 = 0.15;
+
+/// Logit per unit difference in average recent win rate
+const FORM_WEIGHT: f32 = 1.0;
+
+/// Logit per premade member difference
+const PREMADE_WEIGHT: f32 = 0.08;
+
+/// Pseudo-games at 50% mixed into small win-rate samples
+const PRIOR_GAMES: f32 = 5.0;
+
+/// Points per tier and per division in the MMR proxy; Master and above share one ladder
+const TIER_POINTS: f32 = 400.0;
+const DIVISION_POINTS: f32 = 100.0;
+
+/// What the estimate knows about one live participant
+#[derive(Debug, Clone)]
+pub struct PlayerFeatures {
+    pub puuid: String,
+    pub team_id: i32,
+    pub mmr: Option<f32>,
+    pub champion_games: u32,
+    pub champion_wins: u32,
+    pub champion_mastery_points: i64,
+    pub recent_games: u32,
+    pub recent_wins: u32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureContribution {
+    pub feature: String,
+    pub ally_value: f32,
+    pub enemy_value: f32,
+    pub weight: f32,
+    /// Contribution to the log-odds of the tracked player's team winning
+    pub logit: f32,
+    /// The same contribution as percentage points away from an even game
+    pub percentage_points: f32,
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WinProbability {
+    pub team_id: i32,
+    pub win_probability: f32,
+    pub contributions: Vec<FeatureContribution>,
+}
+
+/// Numeric MMR proxy from solo-queue tier, division and LP; `None` when unranked
+pub fn mmr_proxy(ranked: &[LeagueEntry]) -> Option<f32> {
+    let entry = ranked.iter().find(|e| e.queue_type == riven::consts::QueueType::RANKED_SOLO_5x5)?;
+    let tier = tier_index(&entry.tier.as_ref()?.to_string())?;
+    let division = match entry.rank.as_ref().map(|r| r.to_string()).as_deref() {
+        Some("I") => 3.0,
+        Some("II") => 2.0,
+        Some("III") => 1.0,
+        _ => 0.0,
+    };
+    // Master+ has no divisions; LP carries the whole ladder
+    let master = tier_index("MASTER").unwrap_or(tier);
+    let base = tier.min(master) as f32 * TIER_POINTS;
+    let division = if tier >= master { 0.0 } else { division * DIVISION_POINTS };
+    Some(base + division + entry.league_points as f32)
+}
+
+impl PlayerFeatures {
+    pub fn new(
+        puuid: &str,
+        team_id: i32,
+        champion_id: i32,
+        ranked: &[LeagueEntry],
+        masteries: &[ChampionMastery],
+        recent: &[Match],
+    ) -> Self {
+        let mut features = PlayerFeatures {
+            puuid: puuid.to_string(),
+            team_id,
+            mmr: mmr_proxy(ranked),
+            champion_games: 0,
+            champion_wins: 0,
+            champion_mastery_points: masteries.iter()
+                .find(|m| i16::from(m.champion_id) as i32 == champion_id)
+                .map_or(0, |m| m.champion_points as i64),
+            recent_games: 0,
+            recent_wins: 0,
+        };
+        for p in recent.iter()
+            .filter(|m| !is_remake(m))
+            .filter_map(|m| m.info.participants.iter().find(|p| p.puuid == puuid))
+        {
+            features.recent_games += 1;
+            features.recent_wins += p.win as u32;
+            if champion_id_of(p) == champion_id {
+                features.champion_games += 1;
+                features.champion_wins += p.win as u32;
+            }
+        }
+        features
+    }
+}
+
+fn shrunk_rate(wins: u32, games: u32) -> f32 {
+    (wins as f32 + PRIOR_GAMES * 0.5) / (games as f32 + PRIOR_GAMES)
+}
+
+/// Recent win rate on the champion plus a bonus for long-term mastery of it
+fn champion_strength(player: &PlayerFeatures) -> f32 {
+    let mastery = (player.champion_mastery_points as f32 / MASTERY_CAP).min(1.0);
+    shrunk_rate(player.champion_wins, player.champion_games) + mastery * MASTERY_BONUS
+}
+
+fn average(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 { None } else { Some(sum / count as f32) }
+}
+
+fn contribution(feature: &str, ally: f32, enemy: f32, weight: f32) -> FeatureContribution {
+    let logit = (ally - enemy) * weight;
+    FeatureContribution {
+        feature: feature.to_string(),
+        ally_value: ally,
+        enemy_value: enemy,
+        weight,
+        logit,
+        percentage_points: (sigmoid(logit) - 0.5) * 100.0,
+    }
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Additive log-odds model of the tracked player's team winning, with each feature's share
+pub fn estimate_win_probability(
+    players: &[PlayerFeatures],
+    premades: &[PremadeGroup],
+    tracked_puuid: &str,
+) -> Option<WinProbability> {
+    let team_id = players.iter().find(|p| p.puuid == tracked_puuid)?.team_id;
+    let side = |ally: bool| players.iter().filter(move |p| (p.team_id == team_id) == ally);
+
+    // Unranked players count as the lobby's ranked average so they don't drag a team down
+    let lobby_mmr = average(players.iter().filter_map(|p| p.mmr)).unwrap_or(0.0);
+    let team_mmr = |ally: bool| average(side(ally).map(|p| p.mmr.unwrap_or(lobby_mmr))).unwrap_or(lobby_mmr) / 100.0;
+    let team_champion = |ally: bool| average(side(ally).map(champion_strength)).unwrap_or(0.5);
+    let team_form = |ally: bool| average(side(ally).map(|p| shrunk_rate(p.recent_wins, p.recent_games))).unwrap_or(0.5);
+    let team_premade = |ally: bool| premades.iter()
+        .filter(|g| (g.team_id == team_id) == ally)
+        .map(|g| g.members.len() as f32)
+        .sum::<f32>();
+
+    let contributions = vec![
+        contribution("rank", team_mmr(true), team_mmr(false), RANK_WEIGHT),
+        contribution("championWinRate", team_champion(true), team_champion(false), CHAMPION_WEIGHT),
+        contribution("recentForm", team_form(true), team_form(false), FORM_WEIGHT),
+        contribution("premade", team_premade(true), team_premade(false), PREMADE_WEIGHT),
+    ];
+    let logit: f32 = contributions.iter().map(|c| c.logit).sum();
+
+    Some(WinProbability {
+        team_id,
+        win_probability: sigmoid(logit) * 100.0,
+        contributions,
+    })
+}